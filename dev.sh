@@ -10,6 +10,18 @@ DATABASE_URL="sqlite://db/database.db"
 
 JWT_SECRET="SOMETHING-TOP-SECRET"
 
+# At least 32 bytes. Move the old value to COOKIE_SECRET_PREVIOUS when rotating.
+COOKIE_SECRET="$(head -c 48 /dev/urandom | base64)"
+COOKIE_SECRET_PREVIOUS=""
+
+# At least 32 bytes. Encrypts issue tracker credentials, so keep it stable.
+CREDENTIALS_SECRET="$(head -c 48 /dev/urandom | base64)"
+
+# From your Slack app, for the /guess command. SLACK_WEBHOOK_URL is optional;
+# breakouts started from Slack post their session summaries there.
+SLACK_SIGNING_SECRET="ADD_YOUR_SLACK_SIGNING_SECRET"
+SLACK_WEBHOOK_URL=""
+
 GOOGLE_CLIENT_ID="ADD_YOUR_CLIENT_ID"
 GOOGLE_CLIENT_SECRET="ADD_YOUR_SECRET"
 GOOGLE_CALLBACK_URL="http://localhost:8080/auth/google/callback"
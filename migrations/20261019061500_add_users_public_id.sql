@@ -0,0 +1,7 @@
+-- The lookup_id doubles as the session credential, so it must never be
+-- shown to other participants. public_id is safe to render in the page.
+ALTER TABLE users ADD COLUMN public_id TEXT NOT NULL DEFAULT '';
+
+UPDATE users SET public_id = lower(hex(randomblob(16)));
+
+CREATE UNIQUE INDEX idx_users_public_id ON users(public_id);
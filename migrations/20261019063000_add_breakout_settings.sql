@@ -0,0 +1,6 @@
+ALTER TABLE breakouts ADD COLUMN title TEXT NOT NULL DEFAULT 'Breakout';
+ALTER TABLE breakouts ADD COLUMN description TEXT NOT NULL DEFAULT '';
+-- Rooms created before facilitators existed have none. Nobody may change
+-- their settings until someone joins and takes the role.
+ALTER TABLE breakouts ADD COLUMN facilitator_id INTEGER REFERENCES users(id) ON DELETE SET NULL;
+ALTER TABLE breakouts ADD COLUMN facilitator_only_controls BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,6 @@
+ALTER TABLE breakouts ADD COLUMN code TEXT NOT NULL DEFAULT '';
+
+-- Existing rooms get a plain but unique code; new ones get a friendly one.
+UPDATE breakouts SET code = 'room-' || id;
+
+CREATE UNIQUE INDEX idx_breakouts_code ON breakouts(code);
@@ -0,0 +1,11 @@
+CREATE TABLE breakout_members (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  breakout_id INTEGER NOT NULL REFERENCES breakouts(id) ON DELETE CASCADE,
+  user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  pinned BOOLEAN NOT NULL DEFAULT FALSE,
+  joined_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  last_active_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  UNIQUE (breakout_id, user_id)
+);
+
+CREATE INDEX idx_breakout_members_user_id ON breakout_members(user_id);
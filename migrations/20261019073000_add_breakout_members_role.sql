@@ -0,0 +1,2 @@
+-- Either 'voter' or 'observer'. Observers listen in without voting.
+ALTER TABLE breakout_members ADD COLUMN role TEXT NOT NULL DEFAULT 'voter';
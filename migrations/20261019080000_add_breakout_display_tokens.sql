@@ -0,0 +1,6 @@
+ALTER TABLE breakouts ADD COLUMN display_token TEXT NOT NULL DEFAULT '';
+
+-- The token is what makes a read-only display link unguessable.
+UPDATE breakouts SET display_token = lower(hex(randomblob(16)));
+
+CREATE UNIQUE INDEX idx_breakouts_display_token ON breakouts(display_token);
@@ -0,0 +1,3 @@
+ALTER TABLE breakouts ADD COLUMN auto_reveal BOOLEAN NOT NULL DEFAULT FALSE;
+-- Seconds to count down before an automatic reveal, where 0 reveals at once.
+ALTER TABLE breakouts ADD COLUMN auto_reveal_countdown INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+-- What happens when the round timer runs out: 'notify' or 'reveal'.
+ALTER TABLE breakouts ADD COLUMN timer_expiry TEXT NOT NULL DEFAULT 'notify';
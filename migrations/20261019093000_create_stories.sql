@@ -0,0 +1,15 @@
+CREATE TABLE stories (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  breakout_id INTEGER NOT NULL REFERENCES breakouts(id) ON DELETE CASCADE,
+  title TEXT NOT NULL,
+  position INTEGER NOT NULL,
+  -- The value the facilitator accepted, once the story has been estimated.
+  estimate TEXT,
+  -- Why the facilitator went with something other than the suggestion.
+  estimate_reason TEXT,
+  estimated_at DATETIME,
+  created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_stories_breakout_id ON stories(breakout_id, position);
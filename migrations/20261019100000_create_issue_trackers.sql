@@ -0,0 +1,24 @@
+CREATE TABLE issue_trackers (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  breakout_id INTEGER NOT NULL UNIQUE REFERENCES breakouts(id) ON DELETE CASCADE,
+  kind TEXT NOT NULL,
+  base_url TEXT NOT NULL,
+  username TEXT NOT NULL DEFAULT '',
+  -- Encrypted with CREDENTIALS_SECRET, never stored in the clear.
+  credentials TEXT NOT NULL,
+  -- Which issues to import, e.g. a JQL filter.
+  query TEXT NOT NULL,
+  -- Where accepted estimates are written back, e.g. a custom field id.
+  estimate_field TEXT NOT NULL,
+  created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+ALTER TABLE stories ADD COLUMN external_key TEXT;
+ALTER TABLE stories ADD COLUMN external_url TEXT;
+-- NULL for stories that don't come from a tracker, otherwise 'pending',
+-- 'synced' or 'failed'.
+ALTER TABLE stories ADD COLUMN sync_status TEXT;
+ALTER TABLE stories ADD COLUMN sync_error TEXT;
+
+CREATE UNIQUE INDEX idx_stories_external_key ON stories(breakout_id, external_key);
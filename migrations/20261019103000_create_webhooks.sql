@@ -0,0 +1,34 @@
+CREATE TABLE webhooks (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  breakout_id INTEGER NOT NULL REFERENCES breakouts(id) ON DELETE CASCADE,
+  url TEXT NOT NULL,
+  -- Signs every delivery. Encrypted with CREDENTIALS_SECRET.
+  secret TEXT NOT NULL,
+  on_round_revealed BOOLEAN NOT NULL DEFAULT TRUE,
+  on_estimate_accepted BOOLEAN NOT NULL DEFAULT TRUE,
+  on_session_ended BOOLEAN NOT NULL DEFAULT TRUE,
+  created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_webhooks_breakout_id ON webhooks(breakout_id);
+
+CREATE TABLE webhook_deliveries (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  webhook_id INTEGER NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE,
+  -- Sent as X-Guess-Delivery so receivers can spot retries.
+  delivery_id TEXT NOT NULL UNIQUE,
+  event TEXT NOT NULL,
+  payload TEXT NOT NULL,
+  -- 'pending' until it is either 'delivered' or we give up and it 'failed'.
+  status TEXT NOT NULL DEFAULT 'pending',
+  attempts INTEGER NOT NULL DEFAULT 0,
+  next_attempt_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  response_status INTEGER,
+  error TEXT,
+  created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_webhook_deliveries_due ON webhook_deliveries(status, next_attempt_at);
+CREATE INDEX idx_webhook_deliveries_webhook_id ON webhook_deliveries(webhook_id);
@@ -0,0 +1,8 @@
+ALTER TABLE breakouts ADD COLUMN intake_token TEXT NOT NULL DEFAULT '';
+
+-- The token is what keeps strangers from posting stories into a breakout.
+UPDATE breakouts SET intake_token = lower(hex(randomblob(16)));
+
+CREATE UNIQUE INDEX idx_breakouts_intake_token ON breakouts(intake_token);
+
+ALTER TABLE stories ADD COLUMN description TEXT;
@@ -0,0 +1,2 @@
+-- A Slack incoming webhook that gets a summary whenever a session ends.
+ALTER TABLE breakouts ADD COLUMN slack_webhook_url TEXT;
@@ -0,0 +1,3 @@
+-- Like slack_webhook_url, for teams on Microsoft Teams or Discord instead.
+ALTER TABLE breakouts ADD COLUMN teams_webhook_url TEXT;
+ALTER TABLE breakouts ADD COLUMN discord_webhook_url TEXT;
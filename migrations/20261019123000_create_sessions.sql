@@ -0,0 +1,60 @@
+-- Sessions are kept once they end so their reports can be shared later.
+CREATE TABLE sessions (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  breakout_id INTEGER NOT NULL REFERENCES breakouts(id) ON DELETE CASCADE,
+  started_at DATETIME NOT NULL,
+  ended_at DATETIME NOT NULL,
+  created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_sessions_breakout_id ON sessions(breakout_id, ended_at);
+
+-- Stories are copied as they were at the time, since they may be renamed
+-- or deleted afterwards.
+CREATE TABLE session_rounds (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  session_id INTEGER NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+  story_id INTEGER REFERENCES stories(id) ON DELETE SET NULL,
+  story_title TEXT,
+  external_key TEXT,
+  started_at DATETIME NOT NULL,
+  revealed_at DATETIME NOT NULL
+);
+
+CREATE INDEX idx_session_rounds_session_id ON session_rounds(session_id);
+
+CREATE TABLE session_votes (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  round_id INTEGER NOT NULL REFERENCES session_rounds(id) ON DELETE CASCADE,
+  name TEXT NOT NULL,
+  -- NULL for anyone who didn't vote that round.
+  vote TEXT
+);
+
+CREATE INDEX idx_session_votes_round_id ON session_votes(round_id);
+
+CREATE TABLE session_estimates (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  session_id INTEGER NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+  story_id INTEGER REFERENCES stories(id) ON DELETE SET NULL,
+  story_title TEXT NOT NULL,
+  external_key TEXT,
+  external_url TEXT,
+  estimate TEXT NOT NULL,
+  reason TEXT,
+  accepted_at DATETIME NOT NULL
+);
+
+CREATE INDEX idx_session_estimates_session_id ON session_estimates(session_id);
+
+CREATE TABLE session_participants (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  session_id INTEGER NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+  user_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+  name TEXT NOT NULL,
+  joined_at DATETIME NOT NULL,
+  seconds_present INTEGER NOT NULL,
+  rounds_voted INTEGER NOT NULL
+);
+
+CREATE INDEX idx_session_participants_session_id ON session_participants(session_id);
@@ -0,0 +1,13 @@
+-- Teams group breakouts so their sessions can be looked at together.
+CREATE TABLE teams (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  lookup_id TEXT NOT NULL UNIQUE,
+  name TEXT NOT NULL,
+  created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+  created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+ALTER TABLE breakouts ADD COLUMN team_id INTEGER REFERENCES teams(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_breakouts_team_id ON breakouts(team_id);
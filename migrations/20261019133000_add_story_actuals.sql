@@ -0,0 +1,8 @@
+-- How much effort the story really took once it was done, in the same units
+-- as its estimate.
+ALTER TABLE stories ADD COLUMN actual REAL;
+ALTER TABLE stories ADD COLUMN actual_recorded_at DATETIME;
+
+-- Where actuals are read from, e.g. a custom field id, or empty to only
+-- record them by hand.
+ALTER TABLE issue_trackers ADD COLUMN actual_field TEXT NOT NULL DEFAULT '';
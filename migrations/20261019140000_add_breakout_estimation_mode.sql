@@ -0,0 +1,3 @@
+-- How participants estimate: 'cards' from the deck, or 'three_point'
+-- optimistic, likely and pessimistic values combined with PERT.
+ALTER TABLE breakouts ADD COLUMN estimation_mode TEXT NOT NULL DEFAULT 'cards';
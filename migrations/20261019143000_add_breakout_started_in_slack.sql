@@ -0,0 +1,3 @@
+-- Breakouts started with /guess post their summaries to the server's own
+-- Slack webhook, unless the facilitator picks another one.
+ALTER TABLE breakouts ADD COLUMN started_in_slack BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,3 @@
+-- Breakouts started with /guess are run by whoever started them. Only
+-- they get a link with this token, and joining with it uses it up.
+ALTER TABLE breakouts ADD COLUMN facilitator_token TEXT;
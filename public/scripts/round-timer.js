@@ -0,0 +1,11 @@
+// Counts down from the deadline the server sent, so the clock stays right
+// for anyone who joined late or missed a tick.
+function updateTimers() {
+  document.querySelectorAll('.timer-clock[data-deadline]').forEach(clock => {
+    const remaining = Math.max(0, Math.ceil((Date.parse(clock.dataset.deadline) - Date.now()) / 1000));
+    clock.textContent = `${Math.floor(remaining / 60)}:${String(remaining % 60).padStart(2, '0')}`;
+  });
+}
+
+setInterval(updateTimers, 1000);
+document.body.addEventListener('htmx:wsAfterMessage', updateTimers);
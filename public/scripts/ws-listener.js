@@ -12,28 +12,58 @@ document.body.addEventListener('htmx:wsBeforeMessage', function(event) {
     case "enable_voting":
       enableVoting();
       break;
+    case "facilitator":
+      document.body.classList.add('is-facilitator');
+      break;
   }
 });
 
 document.body.addEventListener('htmx:wsAfterMessage', function() {
-  if (toggle_votes_btn && toggle_votes_btn.textContent.trim() === 'Start Voting') {
+  showVotingMode();
+
+  if (isObserving() || (toggle_votes_btn && toggle_votes_btn.textContent.trim() === 'Start Voting')) {
     disableVoting();
   } else {
-    card_list.querySelectorAll('button').forEach(button => {
-      button.disabled = false;
+    votingControls().forEach(control => {
+      control.disabled = false;
     });
   }
 });
 
+function isObserving() {
+  const publicId = document.body.dataset.publicId;
+  return !!document.querySelector(`#observers #user-${publicId}`);
+}
+
+// Shows the cards or the three-point form, whichever the breakout uses.
+function showVotingMode() {
+  if (!votes.dataset.threePoint) return;
+
+  const threePoint = votes.dataset.threePoint === 'true';
+  if (three_point.hidden === threePoint) {
+    card_list.hidden = threePoint;
+    three_point.hidden = !threePoint;
+    three_point.reset();
+  }
+}
+
+function votingControls() {
+  return [
+    ...card_list.querySelectorAll('button'),
+    ...three_point.querySelectorAll('input:not([type=hidden]), button'),
+  ];
+}
+
 function disableVoting() {
-  card_list.querySelectorAll('button').forEach(button => {
-    button.disabled = true;
+  votingControls().forEach(control => {
+    control.disabled = true;
   });
 }
 
 function enableVoting() {
-  card_list.querySelectorAll('button').forEach(button => {
-    button.disabled = false;
-    button.classList.remove('voted');
+  votingControls().forEach(control => {
+    control.disabled = false;
+    control.classList.remove('voted');
   });
+  three_point.reset();
 }
@@ -1,11 +1,18 @@
-use sqlx::SqlitePool;
+use sqlx::{SqlitePool, error::DatabaseError};
 use std::sync::Arc;
 
 use crate::{
-    domain::breakout::{Breakout, NewBreakout},
+    domain::{
+        breakout::{Breakout, NewBreakout, UpdateBreakout},
+        join_code,
+        notification::UpdateNotifications,
+    },
     infrastructure::db::BreakoutRepository,
 };
 
+/// How many join codes we try before giving up on creating a breakout.
+const CODE_ATTEMPTS: usize = 6;
+
 pub struct BreakoutService {
     breakout_repository: BreakoutRepository,
 }
@@ -20,7 +27,81 @@ impl BreakoutService {
         self.breakout_repository.find_by_lookup_id(&lookup_id).await
     }
 
+    pub async fn find_by_code(&self, code: &str) -> Result<Breakout, sqlx::Error> {
+        self.breakout_repository.find_by_code(code).await
+    }
+
+    pub async fn find_by_display_token(&self, token: &str) -> Result<Breakout, sqlx::Error> {
+        self.breakout_repository.find_by_display_token(token).await
+    }
+
+    pub async fn find_by_intake_token(&self, token: &str) -> Result<Breakout, sqlx::Error> {
+        self.breakout_repository.find_by_intake_token(token).await
+    }
+
+    /// Creates the breakout, picking a new join code if the generated one
+    /// happens to be taken already. If that keeps happening, the last
+    /// attempt's error is returned.
     pub async fn create(&self, breakout: &NewBreakout) -> Result<Breakout, sqlx::Error> {
-        self.breakout_repository.create(breakout).await
+        let with_code = |code: String| NewBreakout {
+            lookup_id: breakout.lookup_id.clone(),
+            title: breakout.title.clone(),
+            code,
+            facilitator_id: breakout.facilitator_id,
+            display_token: breakout.display_token.clone(),
+            intake_token: breakout.intake_token.clone(),
+            started_in_slack: breakout.started_in_slack,
+            facilitator_token: breakout.facilitator_token.clone(),
+        };
+
+        let mut code = breakout.code.clone();
+        for _ in 1..CODE_ATTEMPTS {
+            match self.breakout_repository.create(&with_code(code)).await {
+                Err(sqlx::Error::Database(e)) if is_code_taken(e.as_ref()) => {
+                    code = join_code::generate();
+                }
+                result => return result,
+            }
+        }
+
+        self.breakout_repository.create(&with_code(code)).await
     }
+
+    pub async fn update(&self, breakout: &UpdateBreakout) -> Result<Breakout, sqlx::Error> {
+        self.breakout_repository.update(breakout).await
+    }
+
+    pub async fn update_notifications(
+        &self,
+        notifications: &UpdateNotifications,
+    ) -> Result<Breakout, sqlx::Error> {
+        self.breakout_repository
+            .update_notifications(notifications)
+            .await
+    }
+
+    pub async fn claim_facilitator(
+        &self,
+        id: i64,
+        user_id: i64,
+        token: Option<&str>,
+    ) -> Result<Option<Breakout>, sqlx::Error> {
+        self.breakout_repository
+            .claim_facilitator(id, user_id, token)
+            .await
+    }
+
+    pub async fn update_team(
+        &self,
+        id: i64,
+        team_id: Option<i64>,
+    ) -> Result<Breakout, sqlx::Error> {
+        self.breakout_repository.update_team(id, team_id).await
+    }
+}
+
+/// Whether the database turned the breakout away because its join code is
+/// taken, rather than for any other reason.
+fn is_code_taken(e: &dyn DatabaseError) -> bool {
+    e.is_unique_violation() && e.message().contains("breakouts.code")
 }
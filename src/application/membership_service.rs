@@ -0,0 +1,62 @@
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+use crate::{
+    domain::membership::{ParticipantRole, RecentBreakout},
+    infrastructure::db::MembershipRepository,
+};
+
+/// How many breakouts we list on the homepage.
+const RECENT_LIMIT: i64 = 10;
+
+pub struct MembershipService {
+    membership_repository: MembershipRepository,
+}
+impl MembershipService {
+    pub fn new(db: &Arc<SqlitePool>) -> Self {
+        Self {
+            membership_repository: MembershipRepository::new(db),
+        }
+    }
+
+    pub async fn touch(&self, breakout_id: i64, user_id: i64) -> Result<(), sqlx::Error> {
+        self.membership_repository.touch(breakout_id, user_id).await
+    }
+
+    /// The user's role in the breakout, defaulting to voter for anyone
+    /// who hasn't joined before.
+    pub async fn find_role(
+        &self,
+        breakout_id: i64,
+        user_id: i64,
+    ) -> Result<ParticipantRole, sqlx::Error> {
+        let role = self
+            .membership_repository
+            .find_role(breakout_id, user_id)
+            .await?;
+        Ok(role.unwrap_or_default())
+    }
+
+    pub async fn set_role(
+        &self,
+        breakout_id: i64,
+        user_id: i64,
+        role: ParticipantRole,
+    ) -> Result<(), sqlx::Error> {
+        self.membership_repository
+            .set_role(breakout_id, user_id, role)
+            .await
+    }
+
+    pub async fn toggle_pin(&self, breakout_id: i64, user_id: i64) -> Result<(), sqlx::Error> {
+        self.membership_repository
+            .toggle_pin(breakout_id, user_id)
+            .await
+    }
+
+    pub async fn find_recent(&self, user_id: i64) -> Result<Vec<RecentBreakout>, sqlx::Error> {
+        self.membership_repository
+            .find_recent(user_id, RECENT_LIMIT)
+            .await
+    }
+}
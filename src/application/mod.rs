@@ -1,5 +1,21 @@
 pub mod breakout_service;
+pub mod membership_service;
+pub mod notification_service;
+pub mod session_service;
+pub mod slack_service;
+pub mod story_service;
+pub mod team_service;
+pub mod tracker_service;
 pub mod user_service;
+pub mod webhook_service;
 
 pub use breakout_service::BreakoutService;
+pub use membership_service::MembershipService;
+pub use notification_service::NotificationService;
+pub use session_service::SessionService;
+pub use slack_service::SlackService;
+pub use story_service::StoryService;
+pub use team_service::TeamService;
+pub use tracker_service::TrackerService;
 pub use user_service::UserService;
+pub use webhook_service::WebhookService;
@@ -0,0 +1,53 @@
+use reqwest::Client;
+use std::{env, time::Duration};
+
+use crate::{
+    domain::{breakout::Breakout, notification::Notifier},
+    infrastructure::chat::{self, slack::SlackNotifier},
+    util::url::public_client,
+};
+
+/// How long a chat gets to answer before we give up on a message.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct NotificationService {
+    http: Client,
+    /// Where breakouts started from Slack post to, unless the facilitator
+    /// picks another Slack webhook. It stays on the server, so it isn't
+    /// shown to or changed by them.
+    slack_webhook_url: Option<String>,
+}
+impl NotificationService {
+    /// Reads the optional `SLACK_WEBHOOK_URL`.
+    pub fn new() -> Self {
+        Self {
+            http: public_client(TIMEOUT),
+            slack_webhook_url: env::var("SLACK_WEBHOOK_URL")
+                .ok()
+                .filter(|url| !url.is_empty()),
+        }
+    }
+
+    /// A notifier for every chat the breakout posts to, if any.
+    pub fn notifiers(&self, breakout: &Breakout) -> Vec<Box<dyn Notifier>> {
+        let mut notifiers = chat::notifiers(&self.http, breakout);
+
+        if let (true, None, Some(webhook_url)) = (
+            breakout.started_in_slack,
+            &breakout.slack_webhook_url,
+            &self.slack_webhook_url,
+        ) {
+            notifiers.push(Box::new(SlackNotifier {
+                http: self.http.clone(),
+                webhook_url: webhook_url.clone(),
+            }));
+        }
+
+        notifiers
+    }
+}
+impl Default for NotificationService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
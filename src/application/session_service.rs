@@ -0,0 +1,46 @@
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+use crate::{
+    domain::session_report::{SavedSession, SessionRecord, SessionReport},
+    infrastructure::db::SessionRepository,
+};
+
+pub struct SessionService {
+    session_repository: SessionRepository,
+}
+impl SessionService {
+    pub fn new(db: &Arc<SqlitePool>) -> Self {
+        Self {
+            session_repository: SessionRepository::new(db),
+        }
+    }
+
+    /// The breakout's finished sessions, latest first.
+    pub async fn find_by_breakout(
+        &self,
+        breakout_id: i64,
+    ) -> Result<Vec<SavedSession>, sqlx::Error> {
+        self.session_repository.find_by_breakout(breakout_id).await
+    }
+
+    pub async fn save(
+        &self,
+        breakout_id: i64,
+        record: &SessionRecord,
+    ) -> Result<SavedSession, sqlx::Error> {
+        self.session_repository.create(breakout_id, record).await
+    }
+
+    pub async fn report(&self, breakout_id: i64, id: i64) -> Result<SessionReport, sqlx::Error> {
+        let session = self.session_repository.find(breakout_id, id).await?;
+
+        Ok(SessionReport::new(
+            session,
+            self.session_repository.rounds(id).await?,
+            self.session_repository.votes(id).await?,
+            self.session_repository.estimates(id).await?,
+            self.session_repository.participants(id).await?,
+        ))
+    }
+}
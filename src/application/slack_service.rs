@@ -0,0 +1,90 @@
+use chrono::Utc;
+use log::warn;
+use reqwest::Client;
+use serde_json::Value;
+use std::{env, time::Duration};
+
+use crate::{
+    domain::notification::NotifierError,
+    error::AppError,
+    infrastructure::chat::slack,
+    util::{crypto, url::public_client},
+};
+
+/// How old a signed request can be before we treat it as a replay.
+const MAX_REQUEST_AGE_SECONDS: u64 = 60 * 5;
+/// How long Slack gets to take a follow-up message.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct SlackService {
+    http: Client,
+    signing_secret: Option<String>,
+}
+impl SlackService {
+    /// Reads `SLACK_SIGNING_SECRET`.
+    pub fn new() -> Self {
+        let signing_secret = env::var("SLACK_SIGNING_SECRET")
+            .ok()
+            .filter(|secret| !secret.is_empty());
+        if signing_secret.is_none() {
+            warn!("SLACK_SIGNING_SECRET not defined, the /guess command is disabled.");
+        }
+
+        Self {
+            http: public_client(TIMEOUT),
+            signing_secret,
+        }
+    }
+
+    /// Checks requests against the given secret instead of reading it from
+    /// the environment.
+    pub fn with_signing_secret(secret: &str) -> Self {
+        Self {
+            http: public_client(TIMEOUT),
+            signing_secret: Some(secret.to_string()),
+        }
+    }
+
+    /// Makes sure a request really came from Slack, recently, by checking
+    /// its `X-Slack-Signature` against `v0:{timestamp}:{body}`.
+    pub fn verify(&self, timestamp: &str, signature: &str, body: &str) -> Result<(), AppError> {
+        self.verify_at(Utc::now().timestamp(), timestamp, signature, body)
+    }
+
+    /// Like `verify`, as if it were `now` in Unix seconds.
+    pub fn verify_at(
+        &self,
+        now: i64,
+        timestamp: &str,
+        signature: &str,
+        body: &str,
+    ) -> Result<(), AppError> {
+        let Some(secret) = &self.signing_secret else {
+            return Err(AppError::NotFound(
+                "Slack isn't set up on this server.".to_string(),
+            ));
+        };
+        let invalid = || AppError::Forbidden("Invalid Slack signature.".to_string());
+
+        let sent_at: i64 = timestamp.parse().map_err(|_| invalid())?;
+        if now.abs_diff(sent_at) > MAX_REQUEST_AGE_SECONDS {
+            return Err(invalid());
+        }
+
+        let signature = signature.strip_prefix("v0=").ok_or_else(invalid)?;
+        match crypto::verify(secret, &format!("v0:{timestamp}:{body}"), signature) {
+            true => Ok(()),
+            false => Err(invalid()),
+        }
+    }
+
+    /// Follows up on a slash command with another message.
+    pub async fn respond(&self, response_url: &str, message: &Value) -> Result<(), NotifierError> {
+        slack::respond(&self.http, response_url, message).await
+    }
+}
+impl Default for SlackService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
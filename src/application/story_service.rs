@@ -0,0 +1,101 @@
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        issue_tracker::{ImportedIssue, SyncStatus},
+        story::{AcceptEstimate, IncomingStory, NewStory, RecordActual, Story},
+    },
+    infrastructure::db::StoryRepository,
+};
+
+pub struct StoryService {
+    story_repository: StoryRepository,
+}
+impl StoryService {
+    pub fn new(db: &Arc<SqlitePool>) -> Self {
+        Self {
+            story_repository: StoryRepository::new(db),
+        }
+    }
+
+    /// The breakout's backlog, in the order it was added.
+    pub async fn find_by_breakout(&self, breakout_id: i64) -> Result<Vec<Story>, sqlx::Error> {
+        self.story_repository.find_by_breakout(breakout_id).await
+    }
+
+    pub async fn find(&self, breakout_id: i64, story_id: i64) -> Result<Story, sqlx::Error> {
+        self.story_repository.find(breakout_id, story_id).await
+    }
+
+    /// Adds any issues that aren't on the backlog yet, returning how many
+    /// were new. Issues with neither a usable title nor a key are skipped.
+    pub async fn import(
+        &self,
+        breakout_id: i64,
+        issues: &[ImportedIssue],
+    ) -> Result<usize, sqlx::Error> {
+        let mut imported = 0;
+        for issue in issues {
+            let Ok(story) = NewStory::imported(breakout_id, issue) else {
+                continue;
+            };
+            if self.story_repository.import(&story, issue).await? {
+                imported += 1;
+            }
+        }
+        Ok(imported)
+    }
+
+    /// Adds a story pushed by another tool, or finds the one it pushed
+    /// before with the same external key. Also returns whether it was added.
+    pub async fn push(&self, story: &IncomingStory) -> Result<(Story, bool), sqlx::Error> {
+        if let Some(created) = self.story_repository.push(story).await? {
+            return Ok((created, true));
+        }
+
+        let existing = self
+            .story_repository
+            .find_by_external_key(
+                story.breakout_id,
+                story.external_key.as_deref().unwrap_or_default(),
+            )
+            .await?;
+        Ok((existing, false))
+    }
+
+    pub async fn set_sync_status(
+        &self,
+        story_id: i64,
+        status: SyncStatus,
+        error: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        self.story_repository
+            .set_sync_status(story_id, status, error)
+            .await
+    }
+
+    pub async fn create(&self, story: &NewStory) -> Result<Story, sqlx::Error> {
+        self.story_repository.create(story).await
+    }
+
+    pub async fn accept_estimate(
+        &self,
+        breakout_id: i64,
+        estimate: &AcceptEstimate,
+    ) -> Result<Story, sqlx::Error> {
+        self.story_repository
+            .accept_estimate(breakout_id, estimate)
+            .await
+    }
+
+    pub async fn record_actual(
+        &self,
+        breakout_id: i64,
+        actual: &RecordActual,
+    ) -> Result<Story, sqlx::Error> {
+        self.story_repository
+            .record_actual(breakout_id, actual)
+            .await
+    }
+}
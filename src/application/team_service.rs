@@ -0,0 +1,78 @@
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        accuracy::Accuracy,
+        breakout::Breakout,
+        team::{HISTORY_LENGTH, NewTeam, Team, TeamHistory},
+    },
+    infrastructure::db::{SessionRepository, StoryRepository, TeamRepository},
+};
+
+pub struct TeamService {
+    team_repository: TeamRepository,
+    session_repository: SessionRepository,
+    story_repository: StoryRepository,
+}
+impl TeamService {
+    pub fn new(db: &Arc<SqlitePool>) -> Self {
+        Self {
+            team_repository: TeamRepository::new(db),
+            session_repository: SessionRepository::new(db),
+            story_repository: StoryRepository::new(db),
+        }
+    }
+
+    pub async fn find(&self, id: i64) -> Result<Team, sqlx::Error> {
+        self.team_repository.find(id).await
+    }
+
+    pub async fn find_by_lookup_id(&self, lookup_id: &str) -> Result<Team, sqlx::Error> {
+        self.team_repository.find_by_lookup_id(lookup_id).await
+    }
+
+    /// The teams the user can put a breakout in.
+    pub async fn find_for_user(&self, user_id: i64) -> Result<Vec<Team>, sqlx::Error> {
+        self.team_repository.find_for_user(user_id).await
+    }
+
+    pub async fn create(&self, team: &NewTeam) -> Result<Team, sqlx::Error> {
+        self.team_repository.create(team).await
+    }
+
+    pub async fn breakouts(&self, team_id: i64) -> Result<Vec<Breakout>, sqlx::Error> {
+        self.team_repository.breakouts(team_id).await
+    }
+
+    /// How the team's latest sessions went, across all of its breakouts.
+    pub async fn history(
+        &self,
+        team_id: i64,
+        breakouts: &[Breakout],
+    ) -> Result<TeamHistory, sqlx::Error> {
+        let repository = &self.session_repository;
+
+        Ok(TeamHistory::new(
+            breakouts,
+            repository.find_by_team(team_id, HISTORY_LENGTH).await?,
+            repository.rounds_by_team(team_id, HISTORY_LENGTH).await?,
+            repository.votes_by_team(team_id, HISTORY_LENGTH).await?,
+            repository
+                .estimates_by_team(team_id, HISTORY_LENGTH)
+                .await?,
+        ))
+    }
+
+    /// How the team's estimates compare with what its stories really took.
+    pub async fn accuracy(
+        &self,
+        team_id: i64,
+        breakouts: &[Breakout],
+    ) -> Result<Accuracy, sqlx::Error> {
+        Ok(Accuracy::new(
+            breakouts,
+            self.story_repository.find_measured_by_team(team_id).await?,
+        ))
+    }
+}
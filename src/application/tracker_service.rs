@@ -0,0 +1,90 @@
+use reqwest::Client;
+use sqlx::SqlitePool;
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+    domain::issue_tracker::{
+        ImportedIssue, IssueTracker, SaveTracker, TrackerConnection, TrackerError,
+    },
+    error::AppError,
+    infrastructure::{db::TrackerRepository, trackers},
+    util::{crypto::CredentialCipher, url::public_client},
+};
+
+/// How long we wait on a tracker before giving up on a request.
+const TIMEOUT: Duration = Duration::from_secs(15);
+
+pub struct TrackerService {
+    tracker_repository: TrackerRepository,
+    cipher: CredentialCipher,
+    http: Client,
+}
+impl TrackerService {
+    pub fn new(db: &Arc<SqlitePool>) -> Self {
+        Self {
+            tracker_repository: TrackerRepository::new(db),
+            cipher: CredentialCipher::new(),
+            http: public_client(TIMEOUT),
+        }
+    }
+
+    pub async fn find_by_breakout(
+        &self,
+        breakout_id: i64,
+    ) -> Result<Option<TrackerConnection>, sqlx::Error> {
+        self.tracker_repository.find_by_breakout(breakout_id).await
+    }
+
+    /// Saves the connection, keeping the stored credentials when no new
+    /// ones were given. They're only kept for the same account on the same
+    /// tracker, so a stored token can't be sent anywhere else.
+    pub async fn save(&self, tracker: &SaveTracker) -> Result<TrackerConnection, AppError> {
+        let credentials = match tracker.secret.is_empty() {
+            false => self.cipher.encrypt(&tracker.secret)?,
+            true => self
+                .tracker_repository
+                .find_by_breakout(tracker.breakout_id)
+                .await?
+                .filter(|existing| {
+                    existing.kind == tracker.kind
+                        && existing.base_url == tracker.base_url
+                        && existing.username == tracker.username
+                })
+                .map(|existing| existing.credentials)
+                .ok_or_else(|| AppError::Validation("Please enter an API token.".to_string()))?,
+        };
+
+        Ok(self.tracker_repository.save(tracker, &credentials).await?)
+    }
+
+    pub async fn fetch_issues(
+        &self,
+        connection: &TrackerConnection,
+    ) -> Result<Vec<ImportedIssue>, AppError> {
+        self.client(connection)?
+            .fetch_issues()
+            .await
+            .map_err(|e| AppError::Validation(format!("Couldn't import issues. {e}")))
+    }
+
+    pub async fn fetch_actual(
+        &self,
+        connection: &TrackerConnection,
+        key: &str,
+    ) -> Result<Option<f64>, TrackerError> {
+        self.client(connection)
+            .map_err(|e| TrackerError(e.message().to_string()))?
+            .fetch_actual(key)
+            .await
+    }
+
+    /// A client for the connection's tracker, signed in with its stored
+    /// credentials.
+    pub fn client(
+        &self,
+        connection: &TrackerConnection,
+    ) -> Result<Box<dyn IssueTracker>, AppError> {
+        let secret = self.cipher.decrypt(&connection.credentials)?;
+        Ok(trackers::client(&self.http, connection, secret))
+    }
+}
@@ -21,13 +21,47 @@ impl UserService {
         Ok(User::from(user))
     }
 
+    pub async fn find_by_public_id(&self, public_id: &str) -> Result<User, sqlx::Error> {
+        let user = self.user_repository.find_by_public_id(public_id).await?;
+        Ok(User::from(user))
+    }
+
     pub async fn create(&self, user: &NewUser) -> Result<User, sqlx::Error> {
         let user = self.user_repository.create(user).await?;
         Ok(User::from(user))
     }
 
+    /// Stores a new user. If another request beat us to it, the existing
+    /// user is returned instead.
+    pub async fn find_or_create(&self, user: &NewUser) -> Result<User, sqlx::Error> {
+        match self.user_repository.create(user).await {
+            Ok(user) => Ok(User::from(user)),
+            Err(_) => self.find_by_lookup_id(&user.lookup_id).await,
+        }
+    }
+
+    /// Stores a guest once they do something that needs them to exist,
+    /// like joining a breakout. Users who are already stored are returned
+    /// as they are.
+    pub async fn persist(&self, user: User) -> Result<User, sqlx::Error> {
+        match user.is_guest() {
+            true => {
+                self.find_or_create(&NewUser {
+                    lookup_id: user.lookup_id,
+                    public_id: user.public_id,
+                })
+                .await
+            }
+            false => Ok(user),
+        }
+    }
+
     pub async fn update(&self, user: &UpdateUser) -> Result<User, sqlx::Error> {
         let user = self.user_repository.update(user).await?;
         Ok(User::from(user))
     }
+
+    pub async fn delete_orphaned_guests(&self) -> Result<u64, sqlx::Error> {
+        self.user_repository.delete_orphaned_guests().await
+    }
 }
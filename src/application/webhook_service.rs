@@ -0,0 +1,200 @@
+use chrono::Utc;
+use reqwest::Client;
+use serde_json::json;
+use sqlx::SqlitePool;
+use std::{fmt::Display, sync::Arc, time::Duration};
+
+use crate::{
+    AppInfo,
+    domain::{
+        breakout::Breakout,
+        room_event::RoomEvent,
+        webhook::{
+            DeliveryLogEntry, DeliveryStatus, NewWebhook, WEBHOOK_LIMIT, Webhook, WebhookDelivery,
+        },
+    },
+    error::AppError,
+    infrastructure::db::WebhookRepository,
+    util::{
+        crypto::{self, CredentialCipher},
+        url::public_client,
+    },
+};
+
+/// How long a receiver gets to answer before the attempt counts as failed.
+const TIMEOUT: Duration = Duration::from_secs(10);
+/// How many deliveries show up in a breakout's delivery log.
+const LOG_LENGTH: i64 = 25;
+
+/// Why a delivery attempt didn't go through.
+#[derive(Debug)]
+pub struct DeliveryError {
+    pub response_status: Option<u16>,
+    pub message: String,
+}
+impl Display for DeliveryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+pub struct WebhookService {
+    webhook_repository: WebhookRepository,
+    cipher: CredentialCipher,
+    http: Client,
+}
+impl WebhookService {
+    pub fn new(db: &Arc<SqlitePool>) -> Self {
+        Self {
+            webhook_repository: WebhookRepository::new(db),
+            cipher: CredentialCipher::new(),
+            http: public_client(TIMEOUT),
+        }
+    }
+
+    pub async fn find_by_breakout(&self, breakout_id: i64) -> Result<Vec<Webhook>, sqlx::Error> {
+        self.webhook_repository.find_by_breakout(breakout_id).await
+    }
+
+    pub async fn recent_deliveries(
+        &self,
+        breakout_id: i64,
+    ) -> Result<Vec<DeliveryLogEntry>, sqlx::Error> {
+        self.webhook_repository
+            .recent_deliveries(breakout_id, LOG_LENGTH)
+            .await
+    }
+
+    /// Creates the webhook with a fresh signing secret, returning the
+    /// secret in the clear as this is the only time anyone gets to see it.
+    pub async fn create(&self, webhook: &NewWebhook) -> Result<(Webhook, String), AppError> {
+        let existing = self.find_by_breakout(webhook.breakout_id).await?;
+        if existing.len() >= WEBHOOK_LIMIT {
+            return Err(AppError::Validation(format!(
+                "A breakout can have at most {WEBHOOK_LIMIT} webhooks."
+            )));
+        }
+
+        let secret = format!(
+            "whsec_{}",
+            rand::random::<[u8; 24]>()
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect::<String>()
+        );
+        let created = self
+            .webhook_repository
+            .create(webhook, &self.cipher.encrypt(&secret)?)
+            .await?;
+
+        Ok((created, secret))
+    }
+
+    pub async fn delete(&self, breakout_id: i64, id: i64) -> Result<bool, sqlx::Error> {
+        self.webhook_repository.delete(breakout_id, id).await
+    }
+
+    /// Queues the event for every one of the breakout's webhooks that wants
+    /// it, returning how many deliveries were queued.
+    pub async fn enqueue(
+        &self,
+        event: &RoomEvent,
+        breakout: &Breakout,
+        app_info: &AppInfo,
+    ) -> Result<usize, sqlx::Error> {
+        let webhooks: Vec<Webhook> = self
+            .find_by_breakout(breakout.id)
+            .await?
+            .into_iter()
+            .filter(|w| w.wants(&event.kind))
+            .collect();
+
+        for webhook in &webhooks {
+            let delivery_id = uuid::Uuid::new_v4().to_string();
+            let payload = json!({
+                "id": delivery_id,
+                "event": event.kind.name(),
+                "occurred_at": event.occurred_at,
+                "breakout": {
+                    "id": breakout.lookup_id,
+                    "title": breakout.title,
+                    "url": app_info.breakout_url(&breakout.lookup_id),
+                },
+                "data": event.kind,
+            });
+
+            self.webhook_repository
+                .enqueue(
+                    webhook.id,
+                    &delivery_id,
+                    event.kind.name(),
+                    &payload.to_string(),
+                )
+                .await?;
+        }
+
+        Ok(webhooks.len())
+    }
+
+    pub async fn find_due(&self, limit: i64) -> Result<Vec<WebhookDelivery>, sqlx::Error> {
+        self.webhook_repository.find_due(limit).await
+    }
+
+    /// Posts the delivery to its webhook, signing `{timestamp}.{payload}`
+    /// so receivers can check it came from us and isn't being replayed.
+    pub async fn deliver(&self, delivery: &WebhookDelivery) -> Result<u16, DeliveryError> {
+        let failed = |message: String| DeliveryError {
+            response_status: None,
+            message,
+        };
+
+        let webhook = self
+            .webhook_repository
+            .find(delivery.webhook_id)
+            .await
+            .map_err(|e| failed(format!("Couldn't find the webhook: {e}")))?;
+        let secret = self
+            .cipher
+            .decrypt(&webhook.secret)
+            .map_err(|_| failed("Couldn't read the signing secret.".to_string()))?;
+
+        let timestamp = Utc::now().timestamp().to_string();
+        let signature = crypto::sign(&secret, &format!("{timestamp}.{}", delivery.payload));
+
+        let response = self
+            .http
+            .post(&webhook.url)
+            .header("Content-Type", "application/json")
+            .header("User-Agent", "guess-rs")
+            .header("X-Guess-Event", &delivery.event)
+            .header("X-Guess-Delivery", &delivery.delivery_id)
+            .header("X-Guess-Timestamp", &timestamp)
+            .header("X-Guess-Signature", format!("sha256={signature}"))
+            .body(delivery.payload.clone())
+            .send()
+            .await
+            .map_err(|e| failed(format!("Request failed: {e}")))?;
+
+        let status = response.status();
+        match status.is_success() {
+            true => Ok(status.as_u16()),
+            false => Err(DeliveryError {
+                response_status: Some(status.as_u16()),
+                message: format!("The receiver responded with {status}."),
+            }),
+        }
+    }
+
+    pub async fn record_attempt(
+        &self,
+        delivery: &WebhookDelivery,
+        status: DeliveryStatus,
+        response_status: Option<u16>,
+        error: Option<&str>,
+        retry_in: u64,
+    ) -> Result<(), sqlx::Error> {
+        self.webhook_repository
+            .record_attempt(delivery.id, status, response_status, error, retry_in)
+            .await
+    }
+}
@@ -0,0 +1,293 @@
+use crate::{
+    domain::{breakout::Breakout, deck::Card, session_report::format_points, story::Story},
+    util::chart::Chart,
+};
+
+/// A story's accepted estimate next to the effort it really took.
+pub struct MeasuredStory {
+    pub title: String,
+    pub breakout_title: String,
+    pub estimate: f64,
+    pub actual: f64,
+}
+impl MeasuredStory {
+    /// How far off the estimate was, e.g. 0.5 when the story took half as
+    /// long again.
+    fn error(&self) -> f64 {
+        (self.actual - self.estimate) / self.estimate
+    }
+}
+
+/// How the stories given one estimate turned out.
+pub struct EstimateBucket {
+    pub estimate: f64,
+    pub count: usize,
+    pub min_actual: f64,
+    pub max_actual: f64,
+    pub mean_actual: f64,
+    pub bias: f64,
+}
+impl EstimateBucket {
+    pub fn estimate_label(&self) -> String {
+        format_points(self.estimate)
+    }
+
+    pub fn mean_label(&self) -> String {
+        format_points(self.mean_actual)
+    }
+
+    pub fn range_label(&self) -> String {
+        match self.min_actual == self.max_actual {
+            true => format_points(self.min_actual),
+            false => format!(
+                "{}–{}",
+                format_points(self.min_actual),
+                format_points(self.max_actual)
+            ),
+        }
+    }
+
+    pub fn bias_label(&self) -> String {
+        bias_label(self.bias)
+    }
+}
+
+/// How well a team's estimates matched what its stories really took.
+/// Only numeric estimates above zero count, as anything else can't be
+/// compared.
+pub struct Accuracy {
+    pub stories: Vec<MeasuredStory>,
+    /// One per estimate, smallest first.
+    pub buckets: Vec<EstimateBucket>,
+    /// The average of how far off each estimate was, so 0.2 means stories
+    /// took 20% more than estimated.
+    pub bias: Option<f64>,
+}
+impl Accuracy {
+    pub fn new(breakouts: &[Breakout], stories: Vec<Story>) -> Self {
+        let stories: Vec<MeasuredStory> = stories
+            .into_iter()
+            .filter_map(|story| {
+                let estimate = match Card::parse(story.estimate.as_deref()?)? {
+                    Card::Number(n) if n > 0.0 => n,
+                    _ => return None,
+                };
+
+                Some(MeasuredStory {
+                    breakout_title: breakouts
+                        .iter()
+                        .find(|b| b.id == story.breakout_id)
+                        .map(|b| b.title.clone())
+                        .unwrap_or_default(),
+                    estimate,
+                    actual: story.actual?,
+                    title: story.title,
+                })
+            })
+            .collect();
+
+        let mut estimates: Vec<f64> = stories.iter().map(|s| s.estimate).collect();
+        estimates.sort_by(f64::total_cmp);
+        estimates.dedup();
+
+        let buckets = estimates
+            .into_iter()
+            .map(|estimate| {
+                let measured: Vec<&MeasuredStory> =
+                    stories.iter().filter(|s| s.estimate == estimate).collect();
+                let actuals = measured.iter().map(|s| s.actual);
+
+                EstimateBucket {
+                    estimate,
+                    count: measured.len(),
+                    min_actual: actuals.clone().fold(f64::INFINITY, f64::min),
+                    max_actual: actuals.clone().fold(0.0, f64::max),
+                    mean_actual: actuals.sum::<f64>() / measured.len() as f64,
+                    bias: measured.iter().map(|s| s.error()).sum::<f64>() / measured.len() as f64,
+                }
+            })
+            .collect();
+
+        let bias = match stories.len() {
+            0 => None,
+            n => Some(stories.iter().map(|s| s.error()).sum::<f64>() / n as f64),
+        };
+
+        Self {
+            stories,
+            buckets,
+            bias,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stories.is_empty()
+    }
+
+    pub fn bias_label(&self) -> String {
+        self.bias.map(bias_label).unwrap_or_else(|| "—".to_string())
+    }
+
+    /// The bias in words, for people who don't read percentages as over or
+    /// under.
+    pub fn bias_summary(&self) -> String {
+        match self.bias {
+            Some(bias) if bias.abs() < 0.05 => {
+                "Stories take about as long as estimated.".to_string()
+            }
+            Some(bias) if bias > 0.0 => format!(
+                "Stories take {:.0}% more than estimated on average.",
+                bias * 100.0
+            ),
+            Some(bias) => format!(
+                "Stories take {:.0}% less than estimated on average.",
+                bias.abs() * 100.0
+            ),
+            None => String::new(),
+        }
+    }
+
+    pub fn chart(&self) -> Chart {
+        let data: Vec<(String, f64, f64)> = self
+            .stories
+            .iter()
+            .map(|s| {
+                (
+                    format!(
+                        "{} ({}): estimated {}, took {}",
+                        s.title,
+                        s.breakout_title,
+                        format_points(s.estimate),
+                        format_points(s.actual)
+                    ),
+                    s.estimate,
+                    s.actual,
+                )
+            })
+            .collect();
+
+        Chart::scatter(
+            "Estimate vs Actual",
+            "Each dot is a story. Above the dashed line it took more than estimated, below it less.",
+            &data,
+            "",
+        )
+    }
+}
+
+fn bias_label(bias: f64) -> String {
+    format!("{:+.0}%", bias * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn story(estimate: Option<&str>, actual: Option<f64>) -> Story {
+        let now = chrono::Utc::now().naive_utc();
+        Story {
+            id: 0,
+            breakout_id: 1,
+            title: "Sign in".to_string(),
+            position: 0,
+            estimate: estimate.map(str::to_string),
+            estimate_reason: None,
+            estimated_at: None,
+            created_at: now,
+            updated_at: now,
+            external_key: None,
+            external_url: None,
+            sync_status: None,
+            sync_error: None,
+            description: None,
+            actual,
+            actual_recorded_at: None,
+        }
+    }
+
+    fn accuracy(stories: &[(Option<&str>, Option<f64>)]) -> Accuracy {
+        Accuracy::new(
+            &[],
+            stories
+                .iter()
+                .map(|(estimate, actual)| story(*estimate, *actual))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn only_measures_numeric_estimates_with_actuals() {
+        let accuracy = accuracy(&[
+            (Some("2"), Some(3.0)),
+            (Some("?"), Some(4.0)),
+            (Some("0"), Some(1.0)),
+            (Some("3"), None),
+            (None, Some(2.0)),
+        ]);
+
+        assert_eq!(accuracy.stories.len(), 1);
+        assert_eq!(accuracy.stories[0].estimate, 2.0);
+    }
+
+    #[test]
+    fn groups_stories_by_estimate() {
+        let accuracy = accuracy(&[
+            (Some("5"), Some(6.0)),
+            (Some("2"), Some(3.0)),
+            (Some("2"), Some(1.0)),
+        ]);
+        let buckets: Vec<_> = accuracy
+            .buckets
+            .iter()
+            .map(|b| {
+                (
+                    b.estimate_label(),
+                    b.count,
+                    b.range_label(),
+                    b.mean_label(),
+                    b.bias_label(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            buckets,
+            [
+                ("2".into(), 2, "1–3".into(), "2".into(), "+0%".into()),
+                ("5".into(), 1, "6".into(), "6".into(), "+20%".into()),
+            ]
+        );
+        assert_eq!(accuracy.bias_label(), "+7%");
+        assert_eq!(
+            accuracy.bias_summary(),
+            "Stories take 7% more than estimated on average."
+        );
+    }
+
+    #[test]
+    fn describes_the_bias_in_words() {
+        let close = accuracy(&[(Some("10"), Some(10.4))]);
+        let under = accuracy(&[(Some("4"), Some(3.0))]);
+
+        assert_eq!(
+            close.bias_summary(),
+            "Stories take about as long as estimated."
+        );
+        assert_eq!(
+            under.bias_summary(),
+            "Stories take 25% less than estimated on average."
+        );
+        assert_eq!(under.bias_label(), "-25%");
+    }
+
+    #[test]
+    fn has_no_bias_without_measured_stories() {
+        let accuracy = accuracy(&[]);
+
+        assert!(accuracy.is_empty());
+        assert_eq!(accuracy.bias, None);
+        assert_eq!(accuracy.bias_label(), "—");
+        assert_eq!(accuracy.bias_summary(), "");
+        assert!(accuracy.chart().is_empty());
+    }
+}
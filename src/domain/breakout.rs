@@ -1,18 +1,225 @@
+use crate::{
+    domain::{join_code, pert::EstimationMode, round_timer::TimerExpiry, user::User},
+    error::AppError,
+    util::text::{has_control_chars, normalize_line, normalize_text},
+};
+
+/// Longest breakout title we accept, counted in characters.
+pub const TITLE_MAX_LENGTH: usize = 80;
+/// Longest breakout description we accept, counted in characters.
+pub const DESCRIPTION_MAX_LENGTH: usize = 500;
+/// Longest countdown before an automatic reveal, in seconds.
+pub const AUTO_REVEAL_COUNTDOWN_MAX: i64 = 10;
+
 pub struct NewBreakout {
     pub lookup_id: String,
+    pub title: String,
+    /// The short, human-friendly code used to join by word of mouth.
+    pub code: String,
+    pub facilitator_id: Option<i64>,
+    /// Grants read-only access to the breakout's display board.
+    pub display_token: String,
+    /// Lets other tools push stories onto the backlog.
+    pub intake_token: String,
+    /// Started with Slack's `/guess`, so it reports back there.
+    pub started_in_slack: bool,
+    /// Lets whoever holds it claim a breakout that has no facilitator yet.
+    pub facilitator_token: Option<String>,
 }
 impl Default for NewBreakout {
     fn default() -> Self {
         Self {
             lookup_id: uuid::Uuid::new_v4().to_string(),
+            title: "Breakout".to_string(),
+            code: join_code::generate(),
+            facilitator_id: None,
+            display_token: uuid::Uuid::new_v4().simple().to_string(),
+            intake_token: uuid::Uuid::new_v4().simple().to_string(),
+            started_in_slack: false,
+            facilitator_token: None,
         }
     }
 }
 
+impl NewBreakout {
+    /// A breakout with a title picked up front, checked the same way as
+    /// when the facilitator changes it later.
+    pub fn titled(title: &str) -> Result<Self, AppError> {
+        let title = normalize_line(title);
+
+        if has_control_chars(&title) {
+            return Err(AppError::Validation(
+                "Titles can't contain control characters.".to_string(),
+            ));
+        }
+        check_title(&title)?;
+
+        Ok(Self {
+            title,
+            ..Self::default()
+        })
+    }
+}
+
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct Breakout {
     pub id: i64,
     pub lookup_id: String,
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
+    pub title: String,
+    pub description: String,
+    pub facilitator_id: Option<i64>,
+    /// When set, only the facilitator can reveal and reset votes.
+    pub facilitator_only_controls: bool,
+    pub code: String,
+    pub display_token: String,
+    /// When set, votes are revealed as soon as everyone has voted.
+    pub auto_reveal: bool,
+    pub auto_reveal_countdown: i64,
+    pub timer_expiry: TimerExpiry,
+    pub intake_token: String,
+    /// Incoming webhooks of the chats that hear about sessions starting
+    /// and ending.
+    pub slack_webhook_url: Option<String>,
+    pub teams_webhook_url: Option<String>,
+    pub discord_webhook_url: Option<String>,
+    pub team_id: Option<i64>,
+    pub estimation_mode: EstimationMode,
+    pub started_in_slack: bool,
+    /// Until it's used, only a join with this token can claim the
+    /// facilitator role.
+    pub facilitator_token: Option<String>,
+}
+impl Breakout {
+    pub fn is_facilitator(&self, user: &User) -> bool {
+        BreakoutSettings::from(self).is_facilitator(user)
+    }
+
+    pub fn can_control(&self, user: &User) -> bool {
+        BreakoutSettings::from(self).can_control(user)
+    }
+}
+
+#[derive(Clone)]
+pub struct UpdateBreakout {
+    pub id: i64,
+    pub title: String,
+    pub description: String,
+    pub facilitator_only_controls: bool,
+    pub auto_reveal: bool,
+    pub auto_reveal_countdown: i64,
+    pub timer_expiry: TimerExpiry,
+    pub estimation_mode: EstimationMode,
+}
+impl UpdateBreakout {
+    /// Normalizes the title and description, making sure both are
+    /// reasonable to show to everyone in the breakout.
+    pub fn validated(mut self) -> Result<Self, AppError> {
+        self.title = normalize_line(&self.title);
+        self.description = normalize_text(&self.description);
+
+        if has_control_chars(&self.title) || has_control_chars(&self.description) {
+            return Err(AppError::Validation(
+                "Titles and descriptions can't contain control characters.".to_string(),
+            ));
+        }
+
+        check_title(&self.title)?;
+
+        if self.description.chars().count() > DESCRIPTION_MAX_LENGTH {
+            return Err(AppError::Validation(format!(
+                "Descriptions can be at most {DESCRIPTION_MAX_LENGTH} characters."
+            )));
+        }
+
+        if !(0..=AUTO_REVEAL_COUNTDOWN_MAX).contains(&self.auto_reveal_countdown) {
+            return Err(AppError::Validation(format!(
+                "The reveal countdown can be at most {AUTO_REVEAL_COUNTDOWN_MAX} seconds."
+            )));
+        }
+
+        Ok(self)
+    }
+}
+impl From<&Breakout> for UpdateBreakout {
+    fn from(value: &Breakout) -> Self {
+        Self {
+            id: value.id,
+            title: value.title.clone(),
+            description: value.description.clone(),
+            facilitator_only_controls: value.facilitator_only_controls,
+            auto_reveal: value.auto_reveal,
+            auto_reveal_countdown: value.auto_reveal_countdown,
+            timer_expiry: value.timer_expiry,
+            estimation_mode: value.estimation_mode,
+        }
+    }
+}
+
+fn check_title(title: &str) -> Result<(), AppError> {
+    if title.is_empty() {
+        return Err(AppError::Validation("Please enter a title.".to_string()));
+    }
+
+    if title.chars().count() > TITLE_MAX_LENGTH {
+        return Err(AppError::Validation(format!(
+            "Titles can be at most {TITLE_MAX_LENGTH} characters."
+        )));
+    }
+
+    Ok(())
+}
+
+/// The parts of a breakout that the live channel needs to know about.
+#[derive(Debug, Clone)]
+pub struct BreakoutSettings {
+    pub title: String,
+    pub description: String,
+    pub facilitator_id: Option<i64>,
+    pub facilitator_only_controls: bool,
+    pub auto_reveal: bool,
+    pub auto_reveal_countdown: u64,
+    pub timer_expiry: TimerExpiry,
+    pub estimation_mode: EstimationMode,
+}
+impl BreakoutSettings {
+    /// Nobody is in charge of a breakout without a facilitator, until the
+    /// next person to join claims it.
+    pub fn is_facilitator(&self, user: &User) -> bool {
+        self.facilitator_id.is_some_and(|id| id == user.id)
+    }
+
+    /// Whether this user may reveal and reset votes.
+    pub fn can_control(&self, user: &User) -> bool {
+        !self.facilitator_only_controls || self.is_facilitator(user)
+    }
+}
+impl Default for BreakoutSettings {
+    fn default() -> Self {
+        Self {
+            title: "Breakout".to_string(),
+            description: String::new(),
+            facilitator_id: None,
+            facilitator_only_controls: false,
+            auto_reveal: false,
+            auto_reveal_countdown: 0,
+            timer_expiry: TimerExpiry::default(),
+            estimation_mode: EstimationMode::default(),
+        }
+    }
+}
+impl From<&Breakout> for BreakoutSettings {
+    fn from(value: &Breakout) -> Self {
+        Self {
+            title: value.title.clone(),
+            description: value.description.clone(),
+            facilitator_id: value.facilitator_id,
+            facilitator_only_controls: value.facilitator_only_controls,
+            auto_reveal: value.auto_reveal,
+            auto_reveal_countdown: value.auto_reveal_countdown.max(0) as u64,
+            timer_expiry: value.timer_expiry,
+            estimation_mode: value.estimation_mode,
+        }
+    }
 }
@@ -1,88 +1,789 @@
 use askama::Template;
-use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use log::error;
+use std::collections::{HashMap, HashSet};
 use tokio::sync::broadcast;
 
-use crate::domain::user::User;
+use crate::domain::{
+    breakout::{Breakout, BreakoutSettings},
+    deck::{Card, Deck},
+    membership::ParticipantRole,
+    pert::{EstimationMode, PertEstimate, ThreePoint},
+    room_event::{
+        CastVote, EstimateAccepted, RoomEvent, RoomEventKind, RoomEvents, RoundRevealed,
+        SessionEnded, SessionStarted, StorySummary,
+    },
+    round_stats::RoundStats,
+    round_timer::{RoundTimer, TimerExpiry, TimerState},
+    session_report::{Attendance, PlayedRound, SessionRecord},
+    story::Story,
+    user::User,
+};
 
 #[derive(Template)]
 #[template(path = "breakout_voters.html")]
 pub struct VotersTemplate<'a> {
     breakout: &'a BreakoutChannel,
-    users: Vec<&'a User>,
+    voters: Vec<Voter<'a>>,
+    observers: Vec<Voter<'a>>,
+    stats: RoundStats,
+}
+
+#[derive(Template)]
+#[template(path = "breakout_display_board.html")]
+pub struct DisplayBoardTemplate<'a> {
+    breakout: &'a BreakoutChannel,
+    voters: Vec<Voter<'a>>,
+    stats: RoundStats,
+}
+
+#[derive(Template)]
+#[template(path = "breakout_story.html")]
+pub struct StoryTemplate<'a> {
+    breakout: &'a BreakoutChannel,
+}
+
+#[derive(Template)]
+#[template(path = "breakout_backlog.html")]
+pub struct BacklogTemplate<'a> {
+    breakout: &'a BreakoutChannel,
+}
+
+#[derive(Template)]
+#[template(path = "breakout_timer.html")]
+pub struct TimerTemplate<'a> {
+    breakout: &'a BreakoutChannel,
+}
+
+#[derive(Template)]
+#[template(path = "breakout_header.html")]
+pub struct HeaderTemplate<'a> {
+    settings: &'a BreakoutSettings,
+}
+
+/// A user as shown in the voters list, with a name that is unique within
+/// the breakout.
+pub struct Voter<'a> {
+    pub user: &'a User,
+    pub name: String,
+    /// Set once votes are shown, for whoever voted lowest or highest.
+    pub outlier: Option<Outlier>,
+    /// Whether the talking stick is with this voter.
+    pub has_floor: bool,
+    /// The voter's three-point estimate, once votes are shown.
+    pub range: Option<ThreePoint>,
+}
+impl Voter<'_> {
+    pub fn is_low(&self) -> bool {
+        self.outlier == Some(Outlier::Low)
+    }
+
+    pub fn is_high(&self) -> bool {
+        self.outlier == Some(Outlier::High)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outlier {
+    Low,
+    High,
+}
+
+/// Hands the floor to each outlier in turn, so the lowest and highest
+/// estimators get to explain themselves.
+#[derive(Debug, Clone)]
+pub struct TalkingStick {
+    /// The public ids of everyone due to speak, in order.
+    pub speakers: Vec<String>,
+    pub current: usize,
+}
+impl TalkingStick {
+    pub fn speaker(&self) -> Option<&str> {
+        self.speakers.get(self.current).map(String::as_str)
+    }
+}
+
+/// A pending automatic reveal. Each countdown gets a new generation, so one
+/// that was cancelled or replaced can tell that it should no longer fire.
+#[derive(Debug, Clone, Copy)]
+pub struct Countdown {
+    pub generation: u64,
+    pub remaining: u64,
+}
+
+/// What is left to do once someone has left the room.
+#[must_use]
+pub struct Departure {
+    /// The countdown to revealing the votes of everyone still there.
+    pub countdown: Option<Countdown>,
+    /// When the room is left empty, the generation of the pending session
+    /// end, so the caller can end the session if nobody comes back.
+    pub session_end: Option<u64>,
+}
+
+/// A stretch of time with people in the breakout, from the first of them
+/// joining until the room has been empty for a while.
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub started_at: DateTime<Utc>,
+    pub rounds: Vec<PlayedRound>,
+    pub estimates: Vec<EstimateAccepted>,
+    pub attendance: Vec<Attendance>,
+    /// When voting on the current round opened.
+    round_started_at: DateTime<Utc>,
+    /// Set while the room is empty, to the generation of the pending end
+    /// and when the last person left.
+    idle: Option<(u64, DateTime<Utc>)>,
+}
+impl Session {
+    fn new(started_at: DateTime<Utc>) -> Self {
+        Self {
+            started_at,
+            rounds: vec![],
+            estimates: vec![],
+            attendance: vec![],
+            round_started_at: started_at,
+            idle: None,
+        }
+    }
+
+    fn arrived(&mut self, user: &User, at: DateTime<Utc>) {
+        match self.attendance.iter_mut().find(|a| a.user_id == user.id) {
+            Some(attendance) => {
+                attendance.name = user.display_name.clone();
+                attendance.arrived(at);
+            }
+            None => self.attendance.push(Attendance {
+                user_id: user.id,
+                name: user.display_name.clone(),
+                joined_at: at,
+                seconds_present: 0,
+                present_since: Some(at),
+                rounds_voted: 0,
+            }),
+        }
+    }
 }
 
 #[derive(Clone)]
 pub struct BreakoutChannel {
     pub tx: broadcast::Sender<String>,
+    /// Where room events go on their way to webhooks and chats.
+    events: RoomEvents,
     pub lookup_id: String,
     pub users: Vec<User>,
     pub show_votes: bool,
+    pub settings: BreakoutSettings,
+    /// The breakout's stories, as last loaded from the database.
+    pub backlog: Vec<Story>,
+    /// The story being estimated right now.
+    pub current_story_id: Option<i64>,
+    pub countdown: Option<Countdown>,
+    pub timer: Option<RoundTimer>,
+    pub deck: Deck,
+    pub talking_stick: Option<TalkingStick>,
+    pub session: Option<Session>,
+    /// Whether most of the room has asked for a break.
+    on_break: bool,
+    generation: u64,
 }
 impl BreakoutChannel {
     pub fn find_or_create<'a>(
         channels: &'a mut HashMap<String, BreakoutChannel>,
+        events: &RoomEvents,
         lookup_id: &str,
     ) -> &'a mut BreakoutChannel {
         channels
             .entry(lookup_id.to_string())
             .or_insert_with(|| BreakoutChannel {
                 tx: broadcast::channel(100).0,
+                events: events.clone(),
                 users: vec![],
                 show_votes: false,
                 lookup_id: lookup_id.to_string(),
+                settings: BreakoutSettings::default(),
+                backlog: vec![],
+                current_story_id: None,
+                countdown: None,
+                timer: None,
+                deck: Deck::default(),
+                talking_stick: None,
+                session: None,
+                on_break: false,
+                generation: 0,
             })
     }
 
+    /// Picks up the latest settings for the breakout, letting everyone know
+    /// if anything they can see has changed.
+    pub fn apply_settings(&mut self, breakout: &Breakout) {
+        let settings = BreakoutSettings::from(breakout);
+        let changed = settings.title != self.settings.title
+            || settings.description != self.settings.description
+            || settings.facilitator_only_controls != self.settings.facilitator_only_controls;
+        let mode_changed = settings.estimation_mode != self.settings.estimation_mode;
+
+        self.settings = settings;
+
+        // Votes cast one way make no sense the other, so start over.
+        if mode_changed {
+            self.new_round();
+        }
+
+        if !self.settings.auto_reveal && self.countdown.is_some() {
+            self.countdown = None;
+            self.send_voters();
+        }
+
+        if changed {
+            match (HeaderTemplate {
+                settings: &self.settings,
+            })
+            .render()
+            {
+                Ok(html) => self.send_html(html),
+                Err(e) => error!("Failed to render header for {}: {e}", self.lookup_id),
+            }
+            self.send_story();
+            self.send_voters();
+        }
+    }
+
+    pub fn story(&self) -> Option<&Story> {
+        self.current_story_id
+            .and_then(|id| self.backlog.iter().find(|s| s.id == id))
+    }
+
+    pub fn estimated_count(&self) -> usize {
+        self.backlog.iter().filter(|s| s.is_estimated()).count()
+    }
+
+    /// Replaces the backlog with a fresh copy, moving on to the first
+    /// unestimated story if the current one is gone or already estimated.
+    pub fn set_backlog(&mut self, backlog: Vec<Story>) {
+        self.backlog = backlog;
+
+        if self.story().is_none_or(|s| s.is_estimated()) {
+            self.current_story_id = self
+                .backlog
+                .iter()
+                .find(|s| !s.is_estimated())
+                .map(|s| s.id);
+        }
+
+        self.send_story();
+        self.send_backlog();
+    }
+
+    /// Moves the breakout on to estimating another story, starting a fresh
+    /// round of voting.
+    pub fn select_story(&mut self, story_id: i64) {
+        if self.current_story_id == Some(story_id) || !self.backlog.iter().any(|s| s.id == story_id)
+        {
+            return;
+        }
+
+        self.current_story_id = Some(story_id);
+        self.new_round();
+        self.send_story();
+        self.send_backlog();
+    }
+
+    /// Records the facilitator's estimate and moves on to the next story
+    /// that still needs one.
+    pub fn estimate_accepted(&mut self, story: &Story, backlog: Vec<Story>) {
+        self.send_event(
+            "estimate_accepted",
+            &format!(
+                "{}: {}",
+                story.title,
+                story.estimate.as_deref().unwrap_or_default()
+            ),
+        );
+
+        let accepted = EstimateAccepted {
+            story: StorySummary::from(story),
+            estimate: story.estimate.clone().unwrap_or_default(),
+            reason: story.estimate_reason.clone(),
+            accepted_at: Utc::now(),
+        };
+        if let Some(session) = self.session.as_mut() {
+            session.estimates.push(accepted.clone());
+        }
+        self.emit(RoomEventKind::EstimateAccepted(accepted));
+
+        let next = backlog
+            .iter()
+            .filter(|s| !s.is_estimated())
+            .find(|s| s.position > story.position)
+            .or_else(|| backlog.iter().find(|s| !s.is_estimated()))
+            .map(|s| s.id);
+
+        self.current_story_id = next;
+        self.new_round();
+        self.set_backlog(backlog);
+    }
+
+    /// What the revealed votes suggest the estimate should be: the
+    /// consensus if there is one, or otherwise the median, rounded up to
+    /// a card in the deck. Three-point estimates suggest their expected
+    /// value instead.
+    pub fn suggested_estimate(&self) -> Option<String> {
+        if !self.show_votes {
+            return None;
+        }
+
+        match self.settings.estimation_mode {
+            EstimationMode::Cards => self
+                .stats()
+                .median
+                .map(|median| self.deck.round_up(median).value()),
+            EstimationMode::ThreePoint => self.pert().map(|pert| pert.expected_label()),
+        }
+    }
+
+    pub fn is_three_point(&self) -> bool {
+        self.settings.estimation_mode == EstimationMode::ThreePoint
+    }
+
+    /// The voters' three-point estimates combined, in three-point mode.
+    pub fn pert(&self) -> Option<PertEstimate> {
+        if !self.is_three_point() {
+            return None;
+        }
+
+        PertEstimate::new(
+            self.users
+                .iter()
+                .filter(|u| !u.is_observer())
+                .filter_map(|u| u.vote.as_deref()),
+        )
+    }
+
+    /// Clears the votes, hiding them again if they were shown.
+    fn new_round(&mut self) {
+        if self.show_votes {
+            self.toggle_votes();
+        } else {
+            self.users.iter_mut().for_each(|u| u.vote = None);
+            self.countdown = None;
+            self.on_break = false;
+            self.restart_round_clock();
+            self.send_voters();
+        }
+    }
+
+    fn restart_round_clock(&mut self) {
+        if let Some(session) = self.session.as_mut() {
+            session.round_started_at = Utc::now();
+        }
+    }
+
     pub fn toggle_votes(&mut self) {
         self.show_votes = !self.show_votes;
+        self.countdown = None;
+        self.talking_stick = None;
 
         if !self.show_votes {
             self.users.iter_mut().for_each(|u| u.vote = None);
+            self.on_break = false;
+            self.restart_round_clock();
             self.send_event("enable_voting", "start voting");
         } else {
             self.send_event("disable_voting", "votes are in");
+            self.round_revealed();
         }
 
-        self.send_html(self.voters_html());
+        self.send_voters();
     }
 
-    pub fn vote(&mut self, user_lookup_id: &str, value: &Option<String>) {
+    fn round_revealed(&mut self) {
+        let stats = self.stats();
+        let revealed = RoundRevealed {
+            story: self.story().map(StorySummary::from),
+            votes: self
+                .participants()
+                .0
+                .into_iter()
+                .map(|v| CastVote {
+                    name: v.name,
+                    vote: v.user.vote.clone(),
+                })
+                .collect(),
+            average: stats.average,
+            median: stats.median,
+            consensus: stats.consensus,
+            suggested_estimate: self.suggested_estimate(),
+            pert: self.pert(),
+        };
+
+        if let Some(session) = self.session.as_mut() {
+            session.rounds.push(PlayedRound {
+                story: revealed.story.clone(),
+                started_at: session.round_started_at,
+                revealed_at: Utc::now(),
+                votes: revealed.votes.clone(),
+            });
+            for user in self.users.iter().filter(|u| u.vote.is_some()) {
+                if let Some(attendance) =
+                    session.attendance.iter_mut().find(|a| a.user_id == user.id)
+                {
+                    attendance.rounds_voted += 1;
+                }
+            }
+        }
+        self.emit(RoomEventKind::RoundRevealed(revealed));
+    }
+
+    /// Records a vote, revealing automatically once everyone has voted if
+    /// the breakout asks for it. When the reveal should wait for a countdown,
+    /// the new countdown is returned so the caller can run it.
+    #[must_use]
+    pub fn vote(&mut self, user_lookup_id: &str, value: &Option<String>) -> Option<Countdown> {
+        let three_point = self.is_three_point();
+        let valid = |v: &str| match three_point {
+            true => ThreePoint::parse(v).is_some(),
+            false => self.deck.contains(v),
+        };
+        if value.as_deref().is_some_and(|v| !valid(v)) {
+            return None;
+        }
+
         if let Some(update_user) = self
             .users
             .iter_mut()
-            .find(|u| u.lookup_id == user_lookup_id)
+            .find(|u| u.lookup_id == user_lookup_id && !u.is_observer())
         {
-            if update_user.vote == value.clone() {
+            // Picking the same card again takes the vote back, but sending
+            // the same three values again just keeps them.
+            if update_user.vote == value.clone() && !three_point {
                 update_user.vote = None;
             } else {
                 update_user.vote = value.clone();
             }
         }
-        self.send_html(self.voters_html());
+
+        let on_break = self.stats().coffee_majority();
+        if on_break && !self.on_break {
+            self.send_event("take_a_break", "most of the team needs a break");
+        }
+        self.on_break = on_break;
+
+        self.auto_reveal()
+    }
+
+    /// Reveals the votes if everyone has voted and the breakout asks for
+    /// it, or starts the countdown to doing so, which is then returned.
+    /// Called whenever the votes or the voters change.
+    fn auto_reveal(&mut self) -> Option<Countdown> {
+        let mut started = None;
+        if !self.everyone_voted() {
+            self.countdown = None;
+        } else if self.settings.auto_reveal && !self.show_votes && self.countdown.is_none() {
+            if self.settings.auto_reveal_countdown == 0 {
+                self.toggle_votes();
+                return None;
+            }
+
+            self.generation += 1;
+            self.countdown = Some(Countdown {
+                generation: self.generation,
+                remaining: self.settings.auto_reveal_countdown,
+            });
+            started = self.countdown;
+        }
+
+        self.send_voters();
+        started
+    }
+
+    /// Moves a countdown along by a second, revealing the votes when it runs
+    /// out. Returns false once the countdown is over, or if it is no longer
+    /// the current one.
+    pub fn tick_countdown(&mut self, generation: u64) -> bool {
+        let Some(countdown) = self.countdown.as_mut() else {
+            return false;
+        };
+        if countdown.generation != generation {
+            return false;
+        }
+
+        countdown.remaining = countdown.remaining.saturating_sub(1);
+        if countdown.remaining == 0 {
+            self.toggle_votes();
+            return false;
+        }
+
+        self.send_voters();
+        true
+    }
+
+    pub fn cancel_countdown(&mut self) {
+        if self.countdown.take().is_some() {
+            self.send_voters();
+        }
+    }
+
+    /// Gives the floor to the lowest voters and then the highest, once the
+    /// votes are out and there is a spread worth discussing.
+    pub fn start_talking_stick(&mut self) {
+        if !self.show_votes {
+            return;
+        }
+
+        let (voters, _) = self.participants();
+        let speakers: Vec<String> = voters
+            .iter()
+            .filter(|v| v.is_low())
+            .chain(voters.iter().filter(|v| v.is_high()))
+            .map(|v| v.user.public_id.clone())
+            .collect();
+
+        if !speakers.is_empty() {
+            self.talking_stick = Some(TalkingStick {
+                speakers,
+                current: 0,
+            });
+            self.send_voters();
+        }
+    }
+
+    /// Passes the talking stick along, putting it away after the last
+    /// speaker.
+    pub fn next_speaker(&mut self) {
+        if let Some(stick) = self.talking_stick.as_mut() {
+            stick.current += 1;
+            if stick.speaker().is_none() {
+                self.talking_stick = None;
+            }
+            self.send_voters();
+        }
+    }
+
+    pub fn end_talking_stick(&mut self) {
+        if self.talking_stick.take().is_some() {
+            self.send_voters();
+        }
+    }
+
+    /// Starts a new round timer, replacing any that was already running.
+    pub fn start_timer(&mut self, seconds: i64) -> RoundTimer {
+        self.generation += 1;
+        let timer = RoundTimer::start(self.generation, seconds);
+        self.timer = Some(timer);
+
+        self.send_timer();
+        timer
+    }
+
+    pub fn pause_timer(&mut self) {
+        if let Some(timer) = self.timer.as_mut().filter(|t| t.is_running()) {
+            self.generation += 1;
+            timer.generation = self.generation;
+            timer.pause();
+            self.send_timer();
+        }
+    }
+
+    /// Resumes a paused timer, returning it so it can be ticked again.
+    pub fn resume_timer(&mut self) -> Option<RoundTimer> {
+        let timer = self.timer.as_mut().filter(|t| t.is_paused())?;
+        timer.resume();
+
+        let timer = *timer;
+        self.send_timer();
+        Some(timer)
+    }
+
+    /// Gives the timer more time. An expired timer starts running again, in
+    /// which case it is returned so it can be ticked.
+    pub fn extend_timer(&mut self, seconds: i64) -> Option<RoundTimer> {
+        let timer = self.timer.as_mut()?;
+        let restarted = timer.is_expired();
+        timer.extend(seconds);
+
+        let timer = *timer;
+        self.send_timer();
+        restarted.then_some(timer)
+    }
+
+    pub fn stop_timer(&mut self) {
+        if self.timer.take().is_some() {
+            self.send_timer();
+        }
+    }
+
+    /// Lets everyone know how long is left on the timer, and deals with it
+    /// running out. Returns false once this timer no longer needs ticking.
+    pub fn tick_timer(&mut self, generation: u64) -> bool {
+        let Some(timer) = self
+            .timer
+            .as_mut()
+            .filter(|t| t.generation == generation && t.is_running())
+        else {
+            return false;
+        };
+
+        let remaining = timer.remaining_seconds();
+        if remaining > 0 {
+            self.send_event("timer_tick", &remaining.to_string());
+            return true;
+        }
+
+        timer.state = TimerState::Expired;
+        self.send_event("timer_expired", "time is up");
+        self.send_timer();
+
+        if self.settings.timer_expiry == TimerExpiry::Reveal && !self.show_votes {
+            self.toggle_votes();
+        }
+        false
+    }
+
+    /// Whether every connected voter has voted. A lone voter doesn't count,
+    /// as there is nobody to wait for.
+    fn everyone_voted(&self) -> bool {
+        let stats = self.stats();
+        stats.eligible > 1 && stats.voted == stats.eligible
     }
 
     pub fn user_changed_name(&mut self, user: &User) {
-        Self::remove_user(self, &user.lookup_id);
-        Self::add_user(self, user);
-        self.send_html(self.voters_html());
+        match self
+            .users
+            .iter_mut()
+            .find(|u| u.lookup_id == user.lookup_id)
+        {
+            Some(existing) => existing.display_name = user.display_name.clone(),
+            None => self.users.push(user.clone()),
+        }
+        self.send_voters();
+    }
+
+    /// Moves someone between voting and observing. Observers lose any vote
+    /// they had cast so it no longer counts, which can leave everyone else
+    /// done voting, so this may start an automatic reveal like `vote`.
+    #[must_use]
+    pub fn set_role(&mut self, user_public_id: &str, role: ParticipantRole) -> Option<Countdown> {
+        if let Some(user) = self
+            .users
+            .iter_mut()
+            .find(|u| u.public_id == user_public_id)
+        {
+            user.role = role;
+            if user.is_observer() {
+                user.vote = None;
+            }
+        }
+        self.auto_reveal()
     }
 
+    /// Stats for everyone that is currently able to vote.
+    pub fn stats(&self) -> RoundStats {
+        RoundStats::new(
+            self.users
+                .iter()
+                .filter(|u| !u.is_observer())
+                .map(|u| u.vote.as_deref()),
+        )
+    }
+
+    /// Adds someone to the room, starting a new session if they are the
+    /// first one in.
     pub fn add_user(&mut self, user: &User) {
         if !self.users.iter().any(|u| u.lookup_id == user.lookup_id) {
             self.users.push(user.clone());
         }
-        self.send_html(self.voters_html());
+
+        let now = Utc::now();
+        if self.session.is_none() {
+            self.session = Some(Session::new(now));
+            self.emit(RoomEventKind::SessionStarted(SessionStarted {
+                started_at: now,
+            }));
+        }
+        if let Some(session) = self.session.as_mut() {
+            session.idle = None;
+            session.arrived(user, now);
+        }
+
+        self.send_voters();
     }
 
-    pub fn remove_user(&mut self, user_lookup_id: &str) {
+    /// Takes someone out of the room. That can leave everyone else done
+    /// voting, so it may start an automatic reveal like `vote`, or leave the
+    /// room empty, so the session can end if nobody comes back.
+    pub fn remove_user(&mut self, user_lookup_id: &str) -> Departure {
+        if let (Some(user), Some(session)) = (
+            self.users.iter().find(|u| u.lookup_id == user_lookup_id),
+            self.session.as_mut(),
+        ) {
+            session
+                .attendance
+                .iter_mut()
+                .filter(|a| a.user_id == user.id)
+                .for_each(|a| a.left(Utc::now()));
+        }
         self.users.retain(|u| u.lookup_id != user_lookup_id);
-        self.send_html(self.voters_html());
+
+        if !self.is_empty() {
+            return Departure {
+                countdown: self.auto_reveal(),
+                session_end: None,
+            };
+        }
+
+        self.send_voters();
+        self.generation += 1;
+        let session_end = self.session.as_mut().map(|session| {
+            session.idle = Some((self.generation, Utc::now()));
+            self.generation
+        });
+        Departure {
+            countdown: None,
+            session_end,
+        }
+    }
+
+    /// Wraps up the session, as long as the room is still empty since the
+    /// given generation, returning what it got through.
+    pub fn end_session(&mut self, generation: u64) -> Option<SessionRecord> {
+        if !self.is_empty()
+            || self
+                .session
+                .as_ref()
+                .is_none_or(|s| s.idle.is_none_or(|(g, _)| g != generation))
+        {
+            return None;
+        }
+
+        let mut session = self.session.take()?;
+        let ended_at = session.idle.map_or_else(Utc::now, |(_, since)| since);
+        session.attendance.iter_mut().for_each(|a| a.left(ended_at));
+
+        self.emit(RoomEventKind::SessionEnded(SessionEnded {
+            started_at: session.started_at,
+            ended_at,
+            rounds: session.rounds.len() as u32,
+            estimates: session.estimates.clone(),
+        }));
+
+        Some(SessionRecord {
+            started_at: session.started_at,
+            ended_at,
+            rounds: session.rounds,
+            estimates: session.estimates,
+            attendance: session.attendance,
+        })
     }
 
     pub fn is_empty(&self) -> bool {
         self.users.is_empty()
     }
 
+    fn emit(&self, kind: RoomEventKind) {
+        self.events.send(RoomEvent::new(&self.lookup_id, kind));
+    }
+
     fn send_event(&self, name: &str, data: &str) {
         let _ = self.tx.send(format!("event: {}\ndata: {}\n\n", name, data));
     }
@@ -91,20 +792,403 @@ impl BreakoutChannel {
         let _ = self.tx.send(html);
     }
 
-    pub fn voters_html(&self) -> String {
-        let mut user_refs: Vec<&User> = self.users.iter().collect();
+    fn send_story(&self) {
+        match self.story_html() {
+            Ok(html) => self.send_html(html),
+            Err(e) => error!("Failed to render story for {}: {e}", self.lookup_id),
+        }
+    }
 
-        user_refs.sort_by(|a, b| {
-            a.display_name
-                .to_lowercase()
-                .cmp(&b.display_name.to_lowercase())
-        });
+    fn send_backlog(&self) {
+        match self.backlog_html() {
+            Ok(html) => self.send_html(html),
+            Err(e) => error!("Failed to render backlog for {}: {e}", self.lookup_id),
+        }
+    }
+
+    fn send_timer(&self) {
+        match self.timer_html() {
+            Ok(html) => self.send_html(html),
+            Err(e) => error!("Failed to render timer for {}: {e}", self.lookup_id),
+        }
+    }
+
+    fn send_voters(&self) {
+        match self.voters_html() {
+            Ok(html) => self.send_html(html),
+            Err(e) => error!("Failed to render voters for {}: {e}", self.lookup_id),
+        }
+    }
+
+    pub fn voters_html(&self) -> Result<String, askama::Error> {
+        let (voters, observers) = self.participants();
 
         VotersTemplate {
             breakout: self,
-            users: user_refs,
+            voters,
+            observers,
+            stats: self.stats(),
+        }
+        .render()
+    }
+
+    pub fn story_html(&self) -> Result<String, askama::Error> {
+        StoryTemplate { breakout: self }.render()
+    }
+
+    pub fn backlog_html(&self) -> Result<String, askama::Error> {
+        BacklogTemplate { breakout: self }.render()
+    }
+
+    pub fn timer_html(&self) -> Result<String, askama::Error> {
+        TimerTemplate { breakout: self }.render()
+    }
+
+    /// The read-only board shown on a shared screen.
+    pub fn display_html(&self) -> Result<String, askama::Error> {
+        let (voters, _) = self.participants();
+
+        DisplayBoardTemplate {
+            breakout: self,
+            voters,
+            stats: self.stats(),
         }
         .render()
-        .unwrap()
+    }
+
+    /// Voters and observers, each sorted by name.
+    fn participants(&self) -> (Vec<Voter<'_>>, Vec<Voter<'_>>) {
+        let (mut observers, mut voters): (Vec<_>, Vec<_>) = self
+            .voters()
+            .into_iter()
+            .partition(|v| v.user.is_observer());
+
+        voters.sort_by_key(|v| v.name.to_lowercase());
+        observers.sort_by_key(|v| v.name.to_lowercase());
+
+        (voters, observers)
+    }
+
+    /// Pairs each user with their display name, numbering anyone who shares
+    /// a name with somebody that joined before them, e.g. "Alex (2)". The
+    /// number skips names already in the room, so it never matches someone
+    /// who is really called "Alex (2)".
+    fn voters(&self) -> Vec<Voter<'_>> {
+        let mut taken: HashSet<String> = self
+            .users
+            .iter()
+            .map(|user| user.display_name.to_lowercase())
+            .collect();
+        let mut seen: HashSet<String> = HashSet::new();
+        let spread = self.stats().spread().filter(|_| self.show_votes);
+        let speaker = self.talking_stick.as_ref().and_then(|s| s.speaker());
+
+        self.users
+            .iter()
+            .map(|user| {
+                let name = match seen.insert(user.display_name.to_lowercase()) {
+                    true => user.display_name.clone(),
+                    false => (2..)
+                        .map(|n| format!("{} ({n})", user.display_name))
+                        .find(|name| taken.insert(name.to_lowercase()))
+                        .unwrap(),
+                };
+
+                let vote = user.vote.as_deref().and_then(Card::parse);
+                let outlier = match (spread, vote) {
+                    (Some((min, _)), Some(Card::Number(n))) if n == min => Some(Outlier::Low),
+                    (Some((_, max)), Some(Card::Number(n))) if n == max => Some(Outlier::High),
+                    _ => None,
+                };
+
+                Voter {
+                    user,
+                    name,
+                    outlier,
+                    has_floor: speaker == Some(user.public_id.as_str()),
+                    range: user
+                        .vote
+                        .as_deref()
+                        .filter(|_| self.show_votes)
+                        .and_then(ThreePoint::parse),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(n: i64) -> User {
+        User {
+            id: n,
+            lookup_id: format!("lookup-{n}"),
+            public_id: format!("public-{n}"),
+            display_name: format!("User {n}"),
+            vote: None,
+            role: ParticipantRole::Voter,
+        }
+    }
+
+    /// A breakout with `people` in it that reveals on its own, after
+    /// `countdown` seconds.
+    fn channel(people: i64, countdown: u64) -> BreakoutChannel {
+        let (events, _) = RoomEvents::new();
+        let mut channels = HashMap::new();
+        let channel = BreakoutChannel::find_or_create(&mut channels, &events, "lookup");
+        channel.settings.auto_reveal = true;
+        channel.settings.auto_reveal_countdown = countdown;
+        for n in 1..=people {
+            channel.add_user(&user(n));
+        }
+
+        channel.clone()
+    }
+
+    fn vote(channel: &mut BreakoutChannel, n: i64, value: &str) -> Option<Countdown> {
+        channel.vote(&format!("lookup-{n}"), &Some(value.to_string()))
+    }
+
+    #[test]
+    fn reveals_once_everyone_has_voted() {
+        let mut channel = channel(3, 0);
+
+        assert!(vote(&mut channel, 1, "3").is_none());
+        assert!(vote(&mut channel, 2, "5").is_none());
+        assert!(!channel.show_votes);
+        assert!(vote(&mut channel, 3, "5").is_none());
+        assert!(channel.show_votes);
+    }
+
+    #[test]
+    fn waits_for_everyone_without_auto_reveal() {
+        let mut channel = channel(2, 0);
+        channel.settings.auto_reveal = false;
+
+        vote(&mut channel, 1, "3");
+        vote(&mut channel, 2, "5");
+
+        assert!(!channel.show_votes);
+    }
+
+    #[test]
+    fn counts_down_before_revealing() {
+        let mut channel = channel(2, 2);
+
+        vote(&mut channel, 1, "3");
+        let countdown = vote(&mut channel, 2, "5").unwrap();
+
+        assert_eq!(countdown.remaining, 2);
+        assert!(channel.tick_countdown(countdown.generation));
+        assert!(!channel.show_votes);
+        assert!(!channel.tick_countdown(countdown.generation));
+        assert!(channel.show_votes);
+    }
+
+    #[test]
+    fn taking_a_vote_back_cancels_the_countdown() {
+        let mut channel = channel(2, 2);
+
+        vote(&mut channel, 1, "3");
+        let countdown = vote(&mut channel, 2, "5").unwrap();
+        assert!(vote(&mut channel, 2, "5").is_none());
+
+        assert!(channel.countdown.is_none());
+        assert!(!channel.tick_countdown(countdown.generation));
+        assert!(!channel.show_votes);
+    }
+
+    #[test]
+    fn reveals_when_the_last_voter_leaves() {
+        let mut channel = channel(3, 0);
+
+        vote(&mut channel, 1, "3");
+        vote(&mut channel, 2, "5");
+        let departure = channel.remove_user("lookup-3");
+
+        assert!(channel.show_votes);
+        assert!(departure.session_end.is_none());
+    }
+
+    #[test]
+    fn counts_down_when_the_last_voter_starts_observing() {
+        let mut channel = channel(3, 3);
+
+        vote(&mut channel, 1, "3");
+        vote(&mut channel, 2, "5");
+        let countdown = channel.set_role("public-3", ParticipantRole::Observer);
+
+        assert_eq!(countdown.map(|c| c.remaining), Some(3));
+        assert!(!channel.show_votes);
+    }
+
+    #[test]
+    fn a_lone_voter_isnt_revealed() {
+        let mut channel = channel(2, 0);
+
+        vote(&mut channel, 1, "3");
+        let departure = channel.remove_user("lookup-2");
+
+        assert!(!channel.show_votes);
+        assert!(departure.countdown.is_none());
+    }
+
+    #[test]
+    fn the_last_one_out_leaves_the_session_to_end() {
+        let mut channel = channel(1, 0);
+
+        let departure = channel.remove_user("lookup-1");
+
+        assert!(departure.session_end.is_some());
+        assert!(channel.is_empty());
+    }
+
+    #[test]
+    fn pausing_stops_the_ticking_timer() {
+        let mut channel = channel(1, 0);
+
+        let started = channel.start_timer(60);
+        channel.pause_timer();
+
+        assert!(!channel.tick_timer(started.generation));
+        let resumed = channel.resume_timer().unwrap();
+        assert_ne!(resumed.generation, started.generation);
+        assert!(channel.tick_timer(resumed.generation));
+    }
+
+    #[test]
+    fn a_new_timer_replaces_the_old_one() {
+        let mut channel = channel(1, 0);
+
+        let first = channel.start_timer(60);
+        let second = channel.start_timer(30);
+
+        assert!(!channel.tick_timer(first.generation));
+        assert!(channel.tick_timer(second.generation));
+    }
+
+    #[test]
+    fn only_an_expired_timer_needs_ticking_again_once_extended() {
+        let mut channel = channel(1, 0);
+
+        let started = channel.start_timer(60);
+        assert!(channel.extend_timer(30).is_none());
+        assert!(channel.tick_timer(started.generation));
+
+        channel.timer.as_mut().unwrap().state = TimerState::Expired;
+        let restarted = channel.extend_timer(30).unwrap();
+        assert!(restarted.is_running());
+        assert!(channel.tick_timer(restarted.generation));
+    }
+
+    #[test]
+    fn running_out_of_time_can_reveal_the_votes() {
+        let mut channel = channel(2, 0);
+        channel.settings.timer_expiry = TimerExpiry::Reveal;
+
+        let timer = channel.start_timer(60);
+        channel.timer.as_mut().unwrap().state = TimerState::Running {
+            deadline: Utc::now(),
+        };
+
+        assert!(!channel.tick_timer(timer.generation));
+        assert!(channel.timer.unwrap().is_expired());
+        assert!(channel.show_votes);
+    }
+
+    /// Reveals the votes of a room where each person voted the given value.
+    fn revealed(votes: &[&str]) -> BreakoutChannel {
+        let mut channel = channel(votes.len() as i64, 0);
+        channel.settings.auto_reveal = false;
+        for (n, value) in (1..).zip(votes) {
+            vote(&mut channel, n, value);
+        }
+        channel.toggle_votes();
+
+        channel
+    }
+
+    fn outliers(channel: &BreakoutChannel) -> Vec<(String, Option<Outlier>)> {
+        let mut voters: Vec<_> = channel
+            .voters()
+            .into_iter()
+            .map(|v| (v.user.public_id.clone(), v.outlier))
+            .collect();
+        voters.sort_by(|a, b| a.0.cmp(&b.0));
+
+        voters
+    }
+
+    #[test]
+    fn marks_the_lowest_and_highest_votes() {
+        let channel = revealed(&["8", "3", "5", "?", "3"]);
+
+        assert_eq!(
+            outliers(&channel),
+            [
+                ("public-1".to_string(), Some(Outlier::High)),
+                ("public-2".to_string(), Some(Outlier::Low)),
+                ("public-3".to_string(), None),
+                ("public-4".to_string(), None),
+                ("public-5".to_string(), Some(Outlier::Low)),
+            ]
+        );
+    }
+
+    #[test]
+    fn has_no_outliers_before_the_reveal_or_on_consensus() {
+        let mut hidden = revealed(&["3", "8"]);
+        hidden.toggle_votes();
+        vote(&mut hidden, 1, "3");
+        vote(&mut hidden, 2, "8");
+        let agreed = revealed(&["5", "5", "?"]);
+
+        assert!(outliers(&hidden).iter().all(|(_, o)| o.is_none()));
+        assert!(outliers(&agreed).iter().all(|(_, o)| o.is_none()));
+    }
+
+    #[test]
+    fn passes_the_talking_stick_from_low_to_high() {
+        let mut channel = revealed(&["8", "3", "5"]);
+
+        channel.start_talking_stick();
+        let stick = channel.talking_stick.clone().unwrap();
+        assert_eq!(stick.speakers, ["public-2", "public-1"]);
+        assert_eq!(stick.speaker(), Some("public-2"));
+
+        channel.next_speaker();
+        assert_eq!(
+            channel.talking_stick.as_ref().and_then(|s| s.speaker()),
+            Some("public-1")
+        );
+
+        channel.next_speaker();
+        assert!(channel.talking_stick.is_none());
+    }
+
+    #[test]
+    fn the_talking_stick_needs_a_revealed_spread() {
+        let mut hidden = channel(2, 0);
+        hidden.settings.auto_reveal = false;
+        vote(&mut hidden, 1, "3");
+        vote(&mut hidden, 2, "8");
+        hidden.start_talking_stick();
+        let mut agreed = revealed(&["5", "5"]);
+        agreed.start_talking_stick();
+
+        assert!(hidden.talking_stick.is_none());
+        assert!(agreed.talking_stick.is_none());
+    }
+
+    #[test]
+    fn a_new_round_puts_the_talking_stick_away() {
+        let mut channel = revealed(&["3", "8"]);
+        channel.start_talking_stick();
+
+        channel.toggle_votes();
+
+        assert!(channel.talking_stick.is_none());
     }
 }
@@ -0,0 +1,151 @@
+use std::cmp::Ordering;
+
+/// A card someone can vote with. Special cards carry a meaning of their own
+/// and are left out of the numeric statistics.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Card {
+    Number(f64),
+    /// "I don't know."
+    Unsure,
+    /// "This is too big to estimate."
+    TooBig,
+    /// "I need a break."
+    Coffee,
+}
+impl Card {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "?" => Some(Self::Unsure),
+            "∞" => Some(Self::TooBig),
+            "☕" => Some(Self::Coffee),
+            number => number
+                .parse::<f64>()
+                .ok()
+                .filter(|n| n.is_finite() && *n >= 0.0)
+                .map(Self::Number),
+        }
+    }
+
+    /// The value sent when voting with this card, which is also its label.
+    pub fn value(&self) -> String {
+        match self {
+            Self::Number(n) => n.to_string(),
+            Self::Unsure => "?".to_string(),
+            Self::TooBig => "∞".to_string(),
+            Self::Coffee => "☕".to_string(),
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Number(_) => "",
+            Self::Unsure => "I don't know",
+            Self::TooBig => "This is too big",
+            Self::Coffee => "I need a break",
+        }
+    }
+
+    pub fn is_special(&self) -> bool {
+        !matches!(self, Self::Number(_))
+    }
+}
+
+/// How many times each card comes up among the values, numbers from low
+/// to high first and the special cards after. Values that aren't cards are
+/// left out.
+pub fn tally<'a>(values: impl IntoIterator<Item = &'a str>) -> Vec<(Card, usize)> {
+    let mut counts: Vec<(Card, usize)> = vec![];
+    for card in values.into_iter().filter_map(Card::parse) {
+        match counts.iter_mut().find(|(c, _)| *c == card) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((card, 1)),
+        }
+    }
+    counts.sort_by(|(a, _), (b, _)| match (a, b) {
+        (Card::Number(a), Card::Number(b)) => a.total_cmp(b),
+        (Card::Number(_), _) => Ordering::Less,
+        (_, Card::Number(_)) => Ordering::Greater,
+        _ => Ordering::Equal,
+    });
+
+    counts
+}
+
+/// The cards a breakout votes with.
+#[derive(Debug, Clone)]
+pub struct Deck {
+    pub cards: Vec<Card>,
+}
+impl Deck {
+    pub fn contains(&self, value: &str) -> bool {
+        Card::parse(value).is_some_and(|card| self.cards.contains(&card))
+    }
+
+    /// The smallest number card that is at least `value`, so a median that
+    /// falls between cards lands on one.
+    pub fn round_up(&self, value: f64) -> Card {
+        self.cards
+            .iter()
+            .filter_map(|card| match card {
+                Card::Number(n) if *n >= value => Some(*n),
+                _ => None,
+            })
+            .reduce(f64::min)
+            .map_or(Card::Number(value), Card::Number)
+    }
+}
+impl Default for Deck {
+    fn default() -> Self {
+        let mut cards: Vec<Card> = [1.0, 2.0, 3.0, 5.0, 8.0, 13.0]
+            .into_iter()
+            .map(Card::Number)
+            .collect();
+        cards.extend([Card::Unsure, Card::TooBig, Card::Coffee]);
+
+        Self { cards }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_numbers_and_special_cards() {
+        assert_eq!(Card::parse("5"), Some(Card::Number(5.0)));
+        assert_eq!(Card::parse("0.5"), Some(Card::Number(0.5)));
+        assert_eq!(Card::parse("?"), Some(Card::Unsure));
+        assert_eq!(Card::parse("∞"), Some(Card::TooBig));
+        assert_eq!(Card::parse("☕"), Some(Card::Coffee));
+
+        for value in ["", "-1", "inf", "NaN", "five"] {
+            assert_eq!(Card::parse(value), None, "{value}");
+        }
+    }
+
+    #[test]
+    fn tallies_numbers_before_special_cards() {
+        let tally = tally(["☕", "8", "?", "3", "8", "nope", "?"]);
+
+        assert_eq!(
+            tally,
+            [
+                (Card::Number(3.0), 1),
+                (Card::Number(8.0), 2),
+                (Card::Coffee, 1),
+                (Card::Unsure, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn rounds_up_to_the_next_number_card() {
+        let deck = Deck::default();
+
+        assert_eq!(deck.round_up(4.0), Card::Number(5.0));
+        assert_eq!(deck.round_up(5.0), Card::Number(5.0));
+        assert_eq!(deck.round_up(20.0), Card::Number(20.0));
+        assert!(deck.contains("?"));
+        assert!(!deck.contains("4"));
+    }
+}
@@ -0,0 +1,167 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::fmt::Display;
+
+use crate::{
+    error::AppError,
+    util::{
+        text::{has_control_chars, normalize_line},
+        url::parse_public_url,
+    },
+};
+
+/// Which service a breakout's backlog comes from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, sqlx::Type, Deserialize)]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum TrackerKind {
+    #[default]
+    Jira,
+    #[sqlx(rename = "github")]
+    #[serde(rename = "github")]
+    GitHub,
+}
+
+/// A breakout's connection to an issue tracker.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct TrackerConnection {
+    pub id: i64,
+    pub breakout_id: i64,
+    pub kind: TrackerKind,
+    pub base_url: String,
+    pub username: String,
+    /// Encrypted; see `CredentialCipher`.
+    pub credentials: String,
+    pub query: String,
+    pub estimate_field: String,
+    /// Where actual effort is read from, or empty when it's only recorded
+    /// by hand.
+    pub actual_field: String,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+#[derive(Clone, Default)]
+pub struct SaveTracker {
+    pub breakout_id: i64,
+    pub kind: TrackerKind,
+    pub base_url: String,
+    pub username: String,
+    /// The plain credentials, or empty to keep the ones already stored.
+    pub secret: String,
+    pub query: String,
+    pub estimate_field: String,
+    pub actual_field: String,
+}
+impl SaveTracker {
+    pub fn validated(mut self) -> Result<Self, AppError> {
+        self.base_url = normalize_line(&self.base_url)
+            .trim_end_matches('/')
+            .to_string();
+        self.username = normalize_line(&self.username);
+        self.secret = self.secret.trim().to_string();
+        self.query = normalize_line(&self.query);
+        self.estimate_field = normalize_line(&self.estimate_field);
+        self.actual_field = normalize_line(&self.actual_field);
+
+        if let Err(message) = parse_public_url(&self.base_url) {
+            return Err(AppError::Validation(message.to_string()));
+        }
+
+        if [
+            &self.base_url,
+            &self.username,
+            &self.secret,
+            &self.query,
+            &self.estimate_field,
+            &self.actual_field,
+        ]
+        .iter()
+        .any(|value| has_control_chars(value))
+        {
+            return Err(AppError::Validation(
+                "Tracker settings can't contain control characters.".to_string(),
+            ));
+        }
+
+        if self.query.is_empty() {
+            return Err(AppError::Validation(
+                "Please enter which issues to import.".to_string(),
+            ));
+        }
+
+        if self.kind == TrackerKind::GitHub && !self.query.contains("repo:") {
+            return Err(AppError::Validation(
+                "Please include the repository to import from, e.g. repo:acme/api.".to_string(),
+            ));
+        }
+
+        if self.estimate_field.is_empty() {
+            return Err(AppError::Validation(
+                "Please enter where estimates should be written.".to_string(),
+            ));
+        }
+
+        if self.kind == TrackerKind::GitHub
+            && !self.estimate_field.starts_with("project:")
+            && self.estimate_field.replace("{}", "").trim().is_empty()
+        {
+            return Err(AppError::Validation(
+                "The estimate label needs some text around {}, e.g. estimate: {}.".to_string(),
+            ));
+        }
+
+        Ok(self)
+    }
+}
+impl From<&TrackerConnection> for SaveTracker {
+    fn from(value: &TrackerConnection) -> Self {
+        Self {
+            breakout_id: value.breakout_id,
+            kind: value.kind,
+            base_url: value.base_url.clone(),
+            username: value.username.clone(),
+            secret: String::new(),
+            query: value.query.clone(),
+            estimate_field: value.estimate_field.clone(),
+            actual_field: value.actual_field.clone(),
+        }
+    }
+}
+
+/// An issue pulled in from a tracker, ready to become a story.
+#[derive(Debug, Clone)]
+pub struct ImportedIssue {
+    pub key: String,
+    pub title: String,
+    pub url: String,
+}
+
+/// Whether a story's estimate made it back to its tracker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+pub enum SyncStatus {
+    Pending,
+    Synced,
+    Failed,
+}
+
+#[derive(Debug)]
+pub struct TrackerError(pub String);
+impl Display for TrackerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Something that holds a team's backlog, which we can import issues from,
+/// write accepted estimates back to and read actual effort from.
+#[async_trait]
+pub trait IssueTracker: Send + Sync {
+    async fn fetch_issues(&self) -> Result<Vec<ImportedIssue>, TrackerError>;
+
+    async fn write_estimate(&self, key: &str, estimate: &str) -> Result<(), TrackerError>;
+
+    /// The issue's actual effort, or `None` when nobody has filled it in.
+    async fn fetch_actual(&self, key: &str) -> Result<Option<f64>, TrackerError>;
+}
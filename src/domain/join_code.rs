@@ -0,0 +1,42 @@
+use rand::{Rng, seq::IndexedRandom};
+
+const ADJECTIVES: &[&str] = &[
+    "able", "bold", "brave", "bright", "calm", "clever", "cozy", "crisp", "daring", "eager",
+    "fair", "fancy", "fresh", "gentle", "giant", "glad", "golden", "grand", "happy", "hardy",
+    "jolly", "keen", "kind", "lively", "lucky", "merry", "mighty", "neat", "nimble", "noble",
+    "plucky", "polite", "proud", "quick", "quiet", "rapid", "rosy", "shiny", "silly", "sleek",
+    "smart", "snappy", "steady", "sunny", "swift", "tidy", "trusty", "witty", "young", "zesty",
+];
+
+const ANIMALS: &[&str] = &[
+    "badger", "beaver", "bison", "camel", "cobra", "condor", "crab", "crane", "dingo", "dolphin",
+    "eagle", "falcon", "ferret", "gecko", "goose", "heron", "hippo", "husky", "ibis", "jaguar",
+    "koala", "lemur", "llama", "lynx", "moose", "narwhal", "newt", "otter", "owl", "panda",
+    "parrot", "pelican", "penguin", "puffin", "quail", "rabbit", "raven", "robin", "salmon",
+    "seal", "sloth", "squid", "tapir", "tiger", "toucan", "turtle", "walrus", "wombat", "yak",
+    "zebra",
+];
+
+/// Generates a pronounceable code such as `brave-otter-482193` that is
+/// easy to read out loud on a call. The number keeps the codes in use too
+/// sparse to find by guessing: there are over two billion of them.
+pub fn generate() -> String {
+    let mut rng = rand::rng();
+    let adjective = ADJECTIVES.choose(&mut rng).unwrap();
+    let animal = ANIMALS.choose(&mut rng).unwrap();
+    let number = rng.random_range(100_000..1_000_000);
+
+    format!("{adjective}-{animal}-{number}")
+}
+
+/// Forgives the usual ways people type a code they heard: stray spaces,
+/// capital letters, and spaces or underscores instead of dashes.
+pub fn normalize(input: &str) -> String {
+    input
+        .trim()
+        .to_lowercase()
+        .split(|c: char| c.is_whitespace() || c == '_' || c == '-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
@@ -0,0 +1,31 @@
+use serde::Deserialize;
+
+use crate::util::time::time_ago;
+
+/// How someone takes part in a breakout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, sqlx::Type, Deserialize)]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ParticipantRole {
+    #[default]
+    Voter,
+    /// Listens in without voting, and is left out of vote counts and
+    /// statistics.
+    Observer,
+}
+
+/// A breakout someone has joined, as listed on their homepage.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct RecentBreakout {
+    pub lookup_id: String,
+    pub title: String,
+    pub code: String,
+    pub pinned: bool,
+    /// The last time anyone was active in the breakout, not just this user.
+    pub last_activity_at: chrono::NaiveDateTime,
+}
+impl RecentBreakout {
+    pub fn last_activity(&self) -> String {
+        time_ago(&self.last_activity_at)
+    }
+}
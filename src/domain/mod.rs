@@ -1,3 +1,17 @@
+pub mod accuracy;
 pub mod breakout;
 pub mod breakout_channel;
+pub mod deck;
+pub mod issue_tracker;
+pub mod join_code;
+pub mod membership;
+pub mod notification;
+pub mod pert;
+pub mod room_event;
+pub mod round_stats;
+pub mod round_timer;
+pub mod session_report;
+pub mod story;
+pub mod team;
 pub mod user;
+pub mod webhook;
@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use std::fmt::Display;
+
+use crate::{
+    domain::{breakout::Breakout, room_event::SessionEnded},
+    error::AppError,
+    util::{
+        text::{has_control_chars, normalize_line},
+        url::parse_public_url,
+    },
+};
+
+/// Longest chat webhook URL we accept, counted in characters.
+pub const WEBHOOK_URL_MAX_LENGTH: usize = 500;
+
+/// Where a breakout tells a team's chat about its sessions.
+#[derive(Clone, Default)]
+pub struct UpdateNotifications {
+    pub id: i64,
+    pub slack_webhook_url: Option<String>,
+    pub teams_webhook_url: Option<String>,
+    pub discord_webhook_url: Option<String>,
+}
+impl UpdateNotifications {
+    pub fn validated(mut self) -> Result<Self, AppError> {
+        for url in [
+            &mut self.slack_webhook_url,
+            &mut self.teams_webhook_url,
+            &mut self.discord_webhook_url,
+        ] {
+            *url = url
+                .take()
+                .map(|url| normalize_line(&url))
+                .filter(|url| !url.is_empty());
+
+            if let Some(url) = url {
+                check_webhook_url(url)?;
+            }
+        }
+
+        Ok(self)
+    }
+}
+impl From<&Breakout> for UpdateNotifications {
+    fn from(value: &Breakout) -> Self {
+        Self {
+            id: value.id,
+            slack_webhook_url: value.slack_webhook_url.clone(),
+            teams_webhook_url: value.teams_webhook_url.clone(),
+            discord_webhook_url: value.discord_webhook_url.clone(),
+        }
+    }
+}
+
+fn check_webhook_url(url: &str) -> Result<(), AppError> {
+    if has_control_chars(url) {
+        return Err(AppError::Validation(
+            "Webhook URLs can't contain control characters.".to_string(),
+        ));
+    }
+
+    if url.chars().count() > WEBHOOK_URL_MAX_LENGTH {
+        return Err(AppError::Validation(format!(
+            "Webhook URLs can be at most {WEBHOOK_URL_MAX_LENGTH} characters."
+        )));
+    }
+
+    if let Err(message) = parse_public_url(url) {
+        return Err(AppError::Validation(message.to_string()));
+    }
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct NotifierError(pub String);
+impl Display for NotifierError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A chat a breakout keeps posted on how its sessions are going. Each
+/// platform only decides how its messages look and where they go.
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// Someone joined an empty breakout, so the team can jump in too.
+    async fn session_started(&self, breakout: &Breakout, url: &str) -> Result<(), NotifierError>;
+
+    /// The session is over; what did it get through?
+    async fn session_summary(
+        &self,
+        breakout: &Breakout,
+        url: &str,
+        session: &SessionEnded,
+    ) -> Result<(), NotifierError>;
+}
@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::session_report::format_points;
+
+/// How participants estimate in a breakout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, sqlx::Type, Deserialize)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum EstimationMode {
+    /// Everyone picks a card from the deck.
+    #[default]
+    Cards,
+    /// Everyone gives an optimistic, most likely and pessimistic value,
+    /// which are combined with PERT.
+    ThreePoint,
+}
+
+/// One participant's three-point estimate. Sent and stored as a vote in the
+/// form "optimistic/likely/pessimistic", e.g. "2/3/8".
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThreePoint {
+    pub optimistic: f64,
+    pub likely: f64,
+    pub pessimistic: f64,
+}
+impl ThreePoint {
+    /// Reads a vote, which needs three numbers from low to high.
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.split('/').map(|part| {
+            part.trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|n| n.is_finite() && *n >= 0.0)
+        });
+        let estimate = Self {
+            optimistic: parts.next()??,
+            likely: parts.next()??,
+            pessimistic: parts.next()??,
+        };
+
+        match parts.next().is_none()
+            && estimate.optimistic <= estimate.likely
+            && estimate.likely <= estimate.pessimistic
+        {
+            true => Some(estimate),
+            false => None,
+        }
+    }
+
+    /// The vote for the three values as given in a form, or `None` when
+    /// any of them is missing.
+    pub fn vote(optimistic: &str, likely: &str, pessimistic: &str) -> Option<String> {
+        let (optimistic, likely, pessimistic) =
+            (optimistic.trim(), likely.trim(), pessimistic.trim());
+
+        match optimistic.is_empty() || likely.is_empty() || pessimistic.is_empty() {
+            true => None,
+            false => Some(format!("{optimistic}/{likely}/{pessimistic}")),
+        }
+    }
+
+    pub fn expected(&self) -> f64 {
+        (self.optimistic + 4.0 * self.likely + self.pessimistic) / 6.0
+    }
+
+    pub fn std_dev(&self) -> f64 {
+        (self.pessimistic - self.optimistic) / 6.0
+    }
+
+    pub fn optimistic_label(&self) -> String {
+        format_points(self.optimistic)
+    }
+
+    pub fn likely_label(&self) -> String {
+        format_points(self.likely)
+    }
+
+    pub fn pessimistic_label(&self) -> String {
+        format_points(self.pessimistic)
+    }
+}
+
+/// The room's three-point estimates combined: the averages of everyone's
+/// optimistic, likely and pessimistic values, weighted the PERT way.
+#[derive(Debug, Clone, Serialize)]
+pub struct PertEstimate {
+    pub optimistic: f64,
+    pub likely: f64,
+    pub pessimistic: f64,
+    pub expected: f64,
+    pub std_dev: f64,
+    /// How many estimates went into it.
+    pub estimates: usize,
+}
+impl PertEstimate {
+    /// Combines every vote that is a valid three-point estimate, or gives
+    /// `None` when there are none.
+    pub fn new<'a>(votes: impl IntoIterator<Item = &'a str>) -> Option<Self> {
+        let estimates: Vec<ThreePoint> = votes.into_iter().filter_map(ThreePoint::parse).collect();
+        if estimates.is_empty() {
+            return None;
+        }
+
+        let mean = |value: fn(&ThreePoint) -> f64| {
+            estimates.iter().map(value).sum::<f64>() / estimates.len() as f64
+        };
+        let combined = ThreePoint {
+            optimistic: mean(|e| e.optimistic),
+            likely: mean(|e| e.likely),
+            pessimistic: mean(|e| e.pessimistic),
+        };
+
+        Some(Self {
+            optimistic: combined.optimistic,
+            likely: combined.likely,
+            pessimistic: combined.pessimistic,
+            expected: combined.expected(),
+            std_dev: combined.std_dev(),
+            estimates: estimates.len(),
+        })
+    }
+
+    pub fn expected_label(&self) -> String {
+        format_points(round(self.expected))
+    }
+
+    pub fn std_dev_label(&self) -> String {
+        format_points(round(self.std_dev))
+    }
+}
+
+fn round(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_three_values_from_low_to_high() {
+        assert_eq!(
+            ThreePoint::parse(" 1 / 2.5 / 8 "),
+            Some(ThreePoint {
+                optimistic: 1.0,
+                likely: 2.5,
+                pessimistic: 8.0,
+            })
+        );
+        assert!(ThreePoint::parse("3/3/3").is_some());
+
+        for value in [
+            "3/2/8", "1/2", "1/2/3/4", "-1/2/3", "1/2/inf", "a/b/c", "5", "",
+        ] {
+            assert_eq!(ThreePoint::parse(value), None, "{value}");
+        }
+    }
+
+    #[test]
+    fn votes_need_all_three_values() {
+        assert_eq!(ThreePoint::vote(" 1", "2 ", "3").as_deref(), Some("1/2/3"));
+        assert_eq!(ThreePoint::vote("1", " ", "3"), None);
+    }
+
+    #[test]
+    fn weights_the_likely_value() {
+        let estimate = ThreePoint::parse("2/3/8").unwrap();
+
+        assert_eq!(estimate.expected(), 22.0 / 6.0);
+        assert_eq!(estimate.std_dev(), 1.0);
+    }
+
+    #[test]
+    fn combines_the_rooms_estimates() {
+        let pert = PertEstimate::new(["2/3/8", "4/5/10", "?", "8/5/2"]).unwrap();
+
+        assert_eq!(pert.estimates, 2);
+        assert_eq!(
+            (pert.optimistic, pert.likely, pert.pessimistic),
+            (3.0, 4.0, 9.0)
+        );
+        assert_eq!(pert.expected_label(), "4.7");
+        assert_eq!(pert.std_dev_label(), "1");
+        assert!(PertEstimate::new(["?", "5"]).is_none());
+    }
+}
@@ -0,0 +1,139 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::{broadcast, mpsc};
+
+use crate::domain::{pert::PertEstimate, story::Story};
+
+/// Something that happened in a breakout which tools outside of it may want
+/// to hear about.
+#[derive(Debug, Clone)]
+pub struct RoomEvent {
+    pub lookup_id: String,
+    pub occurred_at: DateTime<Utc>,
+    pub kind: RoomEventKind,
+}
+impl RoomEvent {
+    pub fn new(lookup_id: &str, kind: RoomEventKind) -> Self {
+        Self {
+            lookup_id: lookup_id.to_string(),
+            occurred_at: Utc::now(),
+            kind,
+        }
+    }
+}
+
+/// Where breakouts send their room events. Anyone can listen in as they
+/// happen, though a listener that falls behind misses some. Webhooks get
+/// every one of them through a queue of their own instead, which never
+/// drops any.
+#[derive(Clone)]
+pub struct RoomEvents {
+    live: broadcast::Sender<RoomEvent>,
+    webhooks: mpsc::UnboundedSender<RoomEvent>,
+}
+impl RoomEvents {
+    /// Returns the receiving end of the webhooks' queue along with it.
+    pub fn new() -> (Self, mpsc::UnboundedReceiver<RoomEvent>) {
+        let (webhooks, queued) = mpsc::unbounded_channel();
+        let events = Self {
+            live: broadcast::channel(256).0,
+            webhooks,
+        };
+
+        (events, queued)
+    }
+
+    pub fn send(&self, event: RoomEvent) {
+        let _ = self.live.send(event.clone());
+        let _ = self.webhooks.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<RoomEvent> {
+        self.live.subscribe()
+    }
+}
+
+/// The event itself, which serializes to the `data` of a webhook payload.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum RoomEventKind {
+    SessionStarted(SessionStarted),
+    RoundRevealed(RoundRevealed),
+    EstimateAccepted(EstimateAccepted),
+    SessionEnded(SessionEnded),
+}
+impl RoomEventKind {
+    /// The name receivers see, e.g. in the `X-Guess-Event` header.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::SessionStarted(_) => "session.started",
+            Self::RoundRevealed(_) => "round.revealed",
+            Self::EstimateAccepted(_) => "estimate.accepted",
+            Self::SessionEnded(_) => "session.ended",
+        }
+    }
+}
+
+/// The parts of a story that are worth sharing outside the breakout.
+#[derive(Debug, Clone, Serialize)]
+pub struct StorySummary {
+    pub id: i64,
+    pub title: String,
+    pub external_key: Option<String>,
+    pub external_url: Option<String>,
+}
+impl From<&Story> for StorySummary {
+    fn from(value: &Story) -> Self {
+        Self {
+            id: value.id,
+            title: value.title.clone(),
+            external_key: value.external_key.clone(),
+            external_url: value.external_url.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CastVote {
+    pub name: String,
+    /// None for anyone who didn't vote this round.
+    pub vote: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RoundRevealed {
+    pub story: Option<StorySummary>,
+    pub votes: Vec<CastVote>,
+    pub average: Option<f64>,
+    pub median: Option<f64>,
+    pub consensus: bool,
+    /// The median rounded up to a card in the deck, or the PERT expected
+    /// value in three-point mode.
+    pub suggested_estimate: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pert: Option<PertEstimate>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EstimateAccepted {
+    pub story: StorySummary,
+    pub estimate: String,
+    /// Why the facilitator went against what the votes suggested.
+    pub reason: Option<String>,
+    pub accepted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionStarted {
+    pub started_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionEnded {
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    /// How many times votes were revealed.
+    pub rounds: u32,
+    /// Every estimate accepted during the session, in order.
+    pub estimates: Vec<EstimateAccepted>,
+}
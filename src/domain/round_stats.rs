@@ -0,0 +1,142 @@
+use crate::domain::deck::Card;
+
+/// A summary of the votes cast by everyone eligible to vote this round.
+pub struct RoundStats {
+    /// How many people can vote.
+    pub eligible: usize,
+    /// How many of them have voted.
+    pub voted: usize,
+    /// The mean of the numeric votes, if there are any.
+    pub average: Option<f64>,
+    /// Whether everyone that voted picked the same number.
+    pub consensus: bool,
+    /// How many voted "?".
+    pub unsure: usize,
+    /// How many voted "∞".
+    pub too_big: usize,
+    /// How many asked for a break.
+    pub coffee: usize,
+    /// The lowest and highest numeric votes.
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// The middle numeric vote, or the mean of the two middle ones.
+    pub median: Option<f64>,
+}
+impl RoundStats {
+    /// Builds the stats from the vote of every eligible participant.
+    pub fn new<'a>(votes: impl IntoIterator<Item = Option<&'a str>>) -> Self {
+        let votes: Vec<Option<&str>> = votes.into_iter().collect();
+        let cast: Vec<Card> = votes
+            .iter()
+            .flatten()
+            .filter_map(|v| Card::parse(v))
+            .collect();
+        let numbers: Vec<f64> = cast
+            .iter()
+            .filter_map(|card| match card {
+                Card::Number(n) => Some(*n),
+                _ => None,
+            })
+            .collect();
+        let mut sorted = numbers.clone();
+        sorted.sort_by(f64::total_cmp);
+        let median = match sorted.len() {
+            0 => None,
+            n if n % 2 == 1 => Some(sorted[n / 2]),
+            n => Some((sorted[n / 2 - 1] + sorted[n / 2]) / 2.0),
+        };
+        let count = |special: Card| cast.iter().filter(|card| **card == special).count();
+
+        Self {
+            eligible: votes.len(),
+            voted: votes.iter().flatten().count(),
+            average: match numbers.is_empty() {
+                true => None,
+                false => Some(numbers.iter().sum::<f64>() / numbers.len() as f64),
+            },
+            consensus: !numbers.is_empty()
+                && numbers.len() == cast.len()
+                && numbers.iter().all(|n| *n == numbers[0]),
+            unsure: count(Card::Unsure),
+            too_big: count(Card::TooBig),
+            coffee: count(Card::Coffee),
+            min: numbers.iter().copied().reduce(f64::min),
+            max: numbers.iter().copied().reduce(f64::max),
+            median,
+        }
+    }
+
+    /// The lowest and highest numeric votes, when they are far enough
+    /// apart to be worth talking about.
+    pub fn spread(&self) -> Option<(f64, f64)> {
+        match (self.min, self.max) {
+            (Some(min), Some(max)) if !self.consensus && min < max => Some((min, max)),
+            _ => None,
+        }
+    }
+
+    /// Whether most of the people that can vote asked for a break.
+    pub fn coffee_majority(&self) -> bool {
+        self.coffee * 2 > self.eligible
+    }
+
+    /// The average rounded to one decimal, without a trailing ".0".
+    pub fn average_label(&self) -> Option<String> {
+        self.average.map(|average| {
+            let rounded = (average * 10.0).round() / 10.0;
+            match rounded.fract() == 0.0 {
+                true => format!("{rounded:.0}"),
+                false => format!("{rounded:.1}"),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaves_special_cards_out_of_the_numbers() {
+        let stats = RoundStats::new([Some("3"), Some("?"), Some("8"), Some("∞"), Some("☕"), None]);
+
+        assert_eq!(stats.eligible, 6);
+        assert_eq!(stats.voted, 5);
+        assert_eq!(stats.average, Some(5.5));
+        assert_eq!(stats.median, Some(5.5));
+        assert_eq!((stats.min, stats.max), (Some(3.0), Some(8.0)));
+        assert_eq!((stats.unsure, stats.too_big, stats.coffee), (1, 1, 1));
+    }
+
+    #[test]
+    fn special_cards_break_consensus() {
+        assert!(RoundStats::new([Some("5"), Some("5"), None]).consensus);
+        assert!(!RoundStats::new([Some("5"), Some("5"), Some("?")]).consensus);
+        assert!(!RoundStats::new([Some("?"), Some("?")]).consensus);
+    }
+
+    #[test]
+    fn has_no_average_without_numbers() {
+        let stats = RoundStats::new([Some("?"), Some("☕")]);
+
+        assert_eq!(stats.average, None);
+        assert_eq!(stats.median, None);
+        assert_eq!(stats.spread(), None);
+        assert_eq!(stats.average_label(), None);
+    }
+
+    #[test]
+    fn wants_coffee_when_most_people_do() {
+        assert!(RoundStats::new([Some("☕"), Some("☕"), Some("3")]).coffee_majority());
+        assert!(!RoundStats::new([Some("☕"), Some("☕"), None, None]).coffee_majority());
+    }
+
+    #[test]
+    fn labels_the_average_to_one_decimal() {
+        let label =
+            |votes: &[&str]| RoundStats::new(votes.iter().map(|v| Some(*v))).average_label();
+
+        assert_eq!(label(&["3", "5"]).as_deref(), Some("4"));
+        assert_eq!(label(&["1", "2", "2"]).as_deref(), Some("1.7"));
+    }
+}
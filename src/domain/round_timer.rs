@@ -0,0 +1,177 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::Deserialize;
+
+/// Longest a round timer can run for, in seconds.
+pub const TIMER_MAX_SECONDS: i64 = 60 * 60;
+
+/// What happens when a round timer runs out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, sqlx::Type, Deserialize)]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum TimerExpiry {
+    /// Lets everyone know that time is up, and nothing else.
+    #[default]
+    Notify,
+    /// Reveals the votes as well.
+    Reveal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerState {
+    Running { deadline: DateTime<Utc> },
+    Paused { remaining: TimeDelta },
+    Expired,
+}
+
+/// A timebox for discussing the current story. The server owns the
+/// deadline, and every change gets a new generation so the task ticking an
+/// older one knows to stop.
+#[derive(Debug, Clone, Copy)]
+pub struct RoundTimer {
+    pub generation: u64,
+    pub state: TimerState,
+}
+impl RoundTimer {
+    pub fn start(generation: u64, seconds: i64) -> Self {
+        Self {
+            generation,
+            state: TimerState::Running {
+                deadline: Utc::now() + TimeDelta::seconds(seconds.clamp(1, TIMER_MAX_SECONDS)),
+            },
+        }
+    }
+
+    pub fn remaining(&self) -> TimeDelta {
+        match self.state {
+            TimerState::Running { deadline } => (deadline - Utc::now()).max(TimeDelta::zero()),
+            TimerState::Paused { remaining } => remaining,
+            TimerState::Expired => TimeDelta::zero(),
+        }
+    }
+
+    /// Whole seconds left, rounded up so "0" only shows once time is up.
+    pub fn remaining_seconds(&self) -> i64 {
+        (self.remaining().num_milliseconds() + 999) / 1000
+    }
+
+    pub fn pause(&mut self) {
+        if let TimerState::Running { .. } = self.state {
+            self.state = TimerState::Paused {
+                remaining: self.remaining(),
+            };
+        }
+    }
+
+    pub fn resume(&mut self) {
+        if let TimerState::Paused { remaining } = self.state {
+            self.state = TimerState::Running {
+                deadline: Utc::now() + remaining,
+            };
+        }
+    }
+
+    /// Adds time to the timer, bringing it back to life if it had expired.
+    pub fn extend(&mut self, seconds: i64) {
+        let extra = TimeDelta::seconds(seconds.clamp(1, TIMER_MAX_SECONDS));
+
+        self.state = match self.state {
+            TimerState::Running { deadline } => TimerState::Running {
+                deadline: deadline + extra,
+            },
+            TimerState::Paused { remaining } => TimerState::Paused {
+                remaining: remaining + extra,
+            },
+            TimerState::Expired => TimerState::Running {
+                deadline: Utc::now() + extra,
+            },
+        };
+    }
+
+    pub fn is_running(&self) -> bool {
+        matches!(self.state, TimerState::Running { .. })
+    }
+
+    pub fn is_paused(&self) -> bool {
+        matches!(self.state, TimerState::Paused { .. })
+    }
+
+    pub fn is_expired(&self) -> bool {
+        matches!(self.state, TimerState::Expired)
+    }
+
+    /// The deadline as an RFC 3339 timestamp, so clients that join late can
+    /// count down on their own.
+    pub fn deadline(&self) -> Option<String> {
+        match self.state {
+            TimerState::Running { deadline } => Some(deadline.to_rfc3339()),
+            _ => None,
+        }
+    }
+
+    /// The time left, e.g. "4:05".
+    pub fn label(&self) -> String {
+        let seconds = self.remaining_seconds();
+        format!("{}:{:02}", seconds / 60, seconds % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starts_within_bounds() {
+        assert_eq!(RoundTimer::start(1, 90).remaining_seconds(), 90);
+        assert_eq!(RoundTimer::start(1, 0).remaining_seconds(), 1);
+        assert_eq!(
+            RoundTimer::start(1, TIMER_MAX_SECONDS + 1).remaining_seconds(),
+            TIMER_MAX_SECONDS
+        );
+    }
+
+    #[test]
+    fn pausing_keeps_the_time_left() {
+        let mut timer = RoundTimer::start(1, 90);
+
+        timer.pause();
+        assert!(timer.is_paused());
+        assert_eq!(timer.remaining_seconds(), 90);
+        assert_eq!(timer.deadline(), None);
+
+        timer.resume();
+        assert!(timer.is_running());
+        assert_eq!(timer.remaining_seconds(), 90);
+    }
+
+    #[test]
+    fn extending_adds_time_in_any_state() {
+        let mut running = RoundTimer::start(1, 60);
+        running.extend(30);
+        assert_eq!(running.remaining_seconds(), 90);
+
+        let mut paused = RoundTimer::start(1, 60);
+        paused.pause();
+        paused.extend(30);
+        assert!(paused.is_paused());
+        assert_eq!(paused.remaining_seconds(), 90);
+
+        let mut expired = RoundTimer::start(1, 60);
+        expired.state = TimerState::Expired;
+        expired.extend(30);
+        assert!(expired.is_running());
+        assert_eq!(expired.remaining_seconds(), 30);
+    }
+
+    #[test]
+    fn labels_round_up_to_the_second() {
+        let timer = RoundTimer {
+            generation: 1,
+            state: TimerState::Paused {
+                remaining: TimeDelta::milliseconds(245_001),
+            },
+        };
+
+        assert_eq!(timer.remaining_seconds(), 246);
+        assert_eq!(timer.label(), "4:06");
+    }
+}
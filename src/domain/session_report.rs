@@ -0,0 +1,513 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+use crate::{
+    domain::{
+        deck::{self, Card},
+        room_event::{CastVote, EstimateAccepted, StorySummary},
+        round_stats::RoundStats,
+    },
+    util::time::duration_label,
+};
+
+/// A round whose votes were revealed, as the session remembers it.
+#[derive(Debug, Clone)]
+pub struct PlayedRound {
+    pub story: Option<StorySummary>,
+    pub started_at: DateTime<Utc>,
+    pub revealed_at: DateTime<Utc>,
+    pub votes: Vec<CastVote>,
+}
+
+/// How much of a session someone was there for.
+#[derive(Debug, Clone)]
+pub struct Attendance {
+    pub user_id: i64,
+    pub name: String,
+    pub joined_at: DateTime<Utc>,
+    /// Time in the room, not counting the stretch they are in right now.
+    pub seconds_present: i64,
+    /// Set while they are in the room.
+    pub present_since: Option<DateTime<Utc>>,
+    pub rounds_voted: u32,
+}
+impl Attendance {
+    pub fn arrived(&mut self, at: DateTime<Utc>) {
+        self.present_since.get_or_insert(at);
+    }
+
+    pub fn left(&mut self, at: DateTime<Utc>) {
+        if let Some(since) = self.present_since.take() {
+            self.seconds_present += (at - since).num_seconds();
+        }
+    }
+}
+
+/// Everything a finished session got through, so its report can be kept.
+#[derive(Debug, Clone)]
+pub struct SessionRecord {
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub rounds: Vec<PlayedRound>,
+    pub estimates: Vec<EstimateAccepted>,
+    pub attendance: Vec<Attendance>,
+}
+impl SessionRecord {
+    /// Whether anything happened that is worth a report.
+    pub fn is_empty(&self) -> bool {
+        self.rounds.is_empty() && self.estimates.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SavedSession {
+    pub id: i64,
+    pub breakout_id: i64,
+    pub started_at: NaiveDateTime,
+    pub ended_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+impl SavedSession {
+    pub fn seconds(&self) -> i64 {
+        (self.ended_at - self.started_at).num_seconds()
+    }
+
+    pub fn duration_label(&self) -> String {
+        duration_label(self.seconds())
+    }
+
+    pub fn date_label(&self) -> String {
+        self.started_at
+            .format("%b %-d, %Y at %H:%M UTC")
+            .to_string()
+    }
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SessionRound {
+    pub id: i64,
+    pub session_id: i64,
+    pub story_id: Option<i64>,
+    pub story_title: Option<String>,
+    pub external_key: Option<String>,
+    pub started_at: NaiveDateTime,
+    pub revealed_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SessionVote {
+    pub id: i64,
+    pub round_id: i64,
+    pub name: String,
+    pub vote: Option<String>,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SessionEstimate {
+    pub id: i64,
+    pub session_id: i64,
+    pub story_id: Option<i64>,
+    pub story_title: String,
+    pub external_key: Option<String>,
+    pub external_url: Option<String>,
+    pub estimate: String,
+    pub reason: Option<String>,
+    pub accepted_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SessionParticipant {
+    pub id: i64,
+    pub session_id: i64,
+    pub user_id: Option<i64>,
+    pub name: String,
+    pub joined_at: NaiveDateTime,
+    pub seconds_present: i64,
+    pub rounds_voted: i64,
+}
+
+/// How many people picked a card in a round.
+pub struct VoteCount {
+    pub vote: String,
+    pub count: usize,
+    /// Relative to the most picked card, for drawing a bar.
+    pub percent: usize,
+}
+
+pub struct RoundReport {
+    pub number: usize,
+    pub story: String,
+    pub duration: String,
+    pub distribution: Vec<VoteCount>,
+    pub votes: Vec<SessionVote>,
+    pub average: Option<String>,
+    pub consensus: bool,
+    /// How many people could have voted but didn't.
+    pub abstained: usize,
+}
+
+pub struct StoryReport {
+    pub title: String,
+    pub external_key: Option<String>,
+    pub external_url: Option<String>,
+    /// None for stories that were discussed but left unestimated.
+    pub estimate: Option<String>,
+    pub reason: Option<String>,
+    pub rounds: usize,
+    pub time_spent: Option<String>,
+}
+
+pub struct ParticipantReport {
+    pub name: String,
+    pub time_present: String,
+    /// How much of the session they were there for.
+    pub attendance: i64,
+    pub rounds_voted: i64,
+}
+
+/// A finished session, put together for reading.
+pub struct SessionReport {
+    pub session: SavedSession,
+    pub rounds: Vec<RoundReport>,
+    pub stories: Vec<StoryReport>,
+    pub participants: Vec<ParticipantReport>,
+    /// The sum of every numeric estimate.
+    pub total_points: String,
+    /// How many estimates weren't numbers, and so aren't in the total.
+    pub unnumbered: usize,
+}
+impl SessionReport {
+    pub fn new(
+        session: SavedSession,
+        rounds: Vec<SessionRound>,
+        votes: Vec<SessionVote>,
+        estimates: Vec<SessionEstimate>,
+        participants: Vec<SessionParticipant>,
+    ) -> Self {
+        let story_label = |round: &SessionRound| match (&round.external_key, &round.story_title) {
+            (Some(key), Some(title)) => format!("{key} {title}"),
+            (None, Some(title)) => title.clone(),
+            _ => "No story".to_string(),
+        };
+
+        let mut stories: Vec<StoryReport> = estimates
+            .iter()
+            .map(|estimate| {
+                let played: Vec<&SessionRound> = rounds
+                    .iter()
+                    .filter(|r| r.story_id.is_some() && r.story_id == estimate.story_id)
+                    .collect();
+                StoryReport {
+                    title: estimate.story_title.clone(),
+                    external_key: estimate.external_key.clone(),
+                    external_url: estimate.external_url.clone(),
+                    estimate: Some(estimate.estimate.clone()),
+                    reason: estimate.reason.clone(),
+                    rounds: played.len(),
+                    time_spent: played.iter().map(|r| r.started_at).min().map(|started_at| {
+                        duration_label((estimate.accepted_at - started_at).num_seconds())
+                    }),
+                }
+            })
+            .collect();
+        let mut discussed: Vec<i64> = vec![];
+        for round in &rounds {
+            let Some(story_id) = round.story_id else {
+                continue;
+            };
+            if discussed.contains(&story_id)
+                || estimates.iter().any(|e| e.story_id == Some(story_id))
+            {
+                continue;
+            }
+            discussed.push(story_id);
+
+            let played: Vec<&SessionRound> = rounds
+                .iter()
+                .filter(|r| r.story_id == Some(story_id))
+                .collect();
+            let started_at = played.iter().map(|r| r.started_at).min();
+            let revealed_at = played.iter().map(|r| r.revealed_at).max();
+            stories.push(StoryReport {
+                title: round.story_title.clone().unwrap_or_default(),
+                external_key: round.external_key.clone(),
+                external_url: None,
+                estimate: None,
+                reason: None,
+                rounds: played.len(),
+                time_spent: started_at
+                    .zip(revealed_at)
+                    .map(|(start, end)| duration_label((end - start).num_seconds())),
+            });
+        }
+
+        let total_points: f64 = estimates
+            .iter()
+            .filter_map(|e| match Card::parse(&e.estimate) {
+                Some(Card::Number(n)) => Some(n),
+                _ => None,
+            })
+            .sum();
+        let unnumbered = estimates
+            .iter()
+            .filter(|e| !matches!(Card::parse(&e.estimate), Some(Card::Number(_))))
+            .count();
+
+        let session_seconds = session.seconds().max(1);
+        let participants = participants
+            .into_iter()
+            .map(|p| ParticipantReport {
+                time_present: duration_label(p.seconds_present),
+                attendance: (p.seconds_present * 100 / session_seconds).clamp(0, 100),
+                rounds_voted: p.rounds_voted,
+                name: p.name,
+            })
+            .collect();
+
+        let rounds = rounds
+            .iter()
+            .enumerate()
+            .map(|(i, round)| {
+                let votes: Vec<SessionVote> = votes
+                    .iter()
+                    .filter(|v| v.round_id == round.id)
+                    .cloned()
+                    .collect();
+                let stats = RoundStats::new(votes.iter().map(|v| v.vote.as_deref()));
+
+                RoundReport {
+                    number: i + 1,
+                    story: story_label(round),
+                    duration: duration_label((round.revealed_at - round.started_at).num_seconds()),
+                    distribution: distribution(&votes),
+                    average: stats.average_label(),
+                    consensus: stats.consensus,
+                    abstained: stats.eligible - stats.voted,
+                    votes,
+                }
+            })
+            .collect();
+
+        Self {
+            session,
+            rounds,
+            stories,
+            participants,
+            total_points: format_points(total_points),
+            unnumbered,
+        }
+    }
+
+    pub fn estimated_count(&self) -> usize {
+        self.stories.iter().filter(|s| s.estimate.is_some()).count()
+    }
+}
+
+/// How many votes each card got.
+fn distribution(votes: &[SessionVote]) -> Vec<VoteCount> {
+    let counts = deck::tally(votes.iter().filter_map(|v| v.vote.as_deref()));
+
+    let most = counts.iter().map(|(_, count)| *count).max().unwrap_or(1);
+    counts
+        .into_iter()
+        .map(|(card, count)| VoteCount {
+            vote: card.value(),
+            count,
+            percent: count * 100 / most,
+        })
+        .collect()
+}
+
+/// A point total without a trailing ".0".
+pub fn format_points(points: f64) -> String {
+    match points.fract() == 0.0 {
+        true => format!("{points:.0}"),
+        false => format!("{points:.1}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+
+    /// `minutes` into a session that started at a fixed time.
+    fn at(minutes: i64) -> NaiveDateTime {
+        DateTime::from_timestamp(1_700_000_000, 0)
+            .unwrap()
+            .naive_utc()
+            + TimeDelta::minutes(minutes)
+    }
+
+    fn round(id: i64, story_id: Option<i64>, started: i64, revealed: i64) -> SessionRound {
+        SessionRound {
+            id,
+            session_id: 1,
+            story_id,
+            story_title: story_id.map(|id| format!("Story {id}")),
+            external_key: None,
+            started_at: at(started),
+            revealed_at: at(revealed),
+        }
+    }
+
+    fn vote(round_id: i64, vote: Option<&str>) -> SessionVote {
+        SessionVote {
+            id: 0,
+            round_id,
+            name: "Ann".to_string(),
+            vote: vote.map(str::to_string),
+        }
+    }
+
+    fn estimate(story_id: i64, estimate: &str, accepted: i64) -> SessionEstimate {
+        SessionEstimate {
+            id: story_id,
+            session_id: 1,
+            story_id: Some(story_id),
+            story_title: format!("Story {story_id}"),
+            external_key: None,
+            external_url: None,
+            estimate: estimate.to_string(),
+            reason: None,
+            accepted_at: at(accepted),
+        }
+    }
+
+    fn participant(name: &str, seconds_present: i64) -> SessionParticipant {
+        SessionParticipant {
+            id: 0,
+            session_id: 1,
+            user_id: None,
+            name: name.to_string(),
+            joined_at: at(0),
+            seconds_present,
+            rounds_voted: 1,
+        }
+    }
+
+    fn report() -> SessionReport {
+        SessionReport::new(
+            SavedSession {
+                id: 1,
+                breakout_id: 1,
+                started_at: at(0),
+                ended_at: at(60),
+                created_at: at(60),
+            },
+            vec![
+                round(1, Some(1), 0, 3),
+                round(2, Some(1), 5, 7),
+                round(3, Some(2), 10, 12),
+                round(4, None, 15, 16),
+            ],
+            vec![
+                vote(1, Some("3")),
+                vote(1, Some("5")),
+                vote(1, Some("?")),
+                vote(1, None),
+                vote(2, Some("5")),
+                vote(2, Some("5")),
+            ],
+            vec![
+                estimate(1, "5", 8),
+                estimate(3, "2.5", 20),
+                estimate(4, "?", 25),
+            ],
+            vec![participant("Ann", 1800), participant("Bob", 7200)],
+        )
+    }
+
+    #[test]
+    fn totals_the_numeric_estimates() {
+        let report = report();
+
+        assert_eq!(report.total_points, "7.5");
+        assert_eq!(report.unnumbered, 1);
+        assert_eq!(report.estimated_count(), 3);
+    }
+
+    #[test]
+    fn lists_estimated_stories_then_discussed_ones() {
+        let report = report();
+        let stories: Vec<_> = report
+            .stories
+            .iter()
+            .map(|s| {
+                (
+                    s.title.as_str(),
+                    s.estimate.as_deref(),
+                    s.rounds,
+                    s.time_spent.as_deref(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            stories,
+            [
+                ("Story 1", Some("5"), 2, Some("8m 0s")),
+                ("Story 3", Some("2.5"), 0, None),
+                ("Story 4", Some("?"), 0, None),
+                ("Story 2", None, 1, Some("2m 0s")),
+            ]
+        );
+    }
+
+    #[test]
+    fn sums_up_each_round() {
+        let report = report();
+        let first = &report.rounds[0];
+        let distribution: Vec<_> = first
+            .distribution
+            .iter()
+            .map(|c| (c.vote.as_str(), c.count, c.percent))
+            .collect();
+
+        assert_eq!(first.story, "Story 1");
+        assert_eq!(first.duration, "3m 0s");
+        assert_eq!(distribution, [("3", 1, 100), ("5", 1, 100), ("?", 1, 100)]);
+        assert_eq!(first.average.as_deref(), Some("4"));
+        assert!(!first.consensus);
+        assert_eq!(first.abstained, 1);
+
+        assert!(report.rounds[1].consensus);
+        assert_eq!(report.rounds[3].story, "No story");
+        assert!(report.rounds[3].distribution.is_empty());
+    }
+
+    #[test]
+    fn attendance_is_a_share_of_the_session() {
+        let report = report();
+        let attendance: Vec<_> = report
+            .participants
+            .iter()
+            .map(|p| (p.name.as_str(), p.time_present.as_str(), p.attendance))
+            .collect();
+
+        assert_eq!(attendance, [("Ann", "30m 0s", 50), ("Bob", "2h 0m", 100)]);
+    }
+
+    #[test]
+    fn counts_time_present_across_visits() {
+        let start = at(0).and_utc();
+        let mut attendance = Attendance {
+            user_id: 1,
+            name: "Ann".to_string(),
+            joined_at: start,
+            seconds_present: 0,
+            present_since: None,
+            rounds_voted: 0,
+        };
+
+        attendance.arrived(start);
+        attendance.arrived(start + TimeDelta::minutes(5));
+        attendance.left(start + TimeDelta::minutes(10));
+        attendance.left(start + TimeDelta::minutes(15));
+        assert_eq!(attendance.seconds_present, 600);
+        assert_eq!(attendance.present_since, None);
+
+        attendance.arrived(start + TimeDelta::minutes(20));
+        attendance.left(start + TimeDelta::minutes(25));
+        assert_eq!(attendance.seconds_present, 900);
+    }
+}
@@ -0,0 +1,387 @@
+use crate::{
+    domain::{
+        issue_tracker::{ImportedIssue, SyncStatus},
+        session_report::format_points,
+    },
+    error::AppError,
+    util::text::{has_control_chars, normalize_line, normalize_text},
+};
+
+/// Longest story title we accept, counted in characters.
+pub const STORY_MAX_LENGTH: usize = 200;
+/// Longest estimate we accept, counted in characters.
+pub const ESTIMATE_MAX_LENGTH: usize = 20;
+/// Longest reason for overriding an estimate, counted in characters.
+pub const REASON_MAX_LENGTH: usize = 500;
+/// Longest story description we accept, counted in characters.
+pub const DESCRIPTION_MAX_LENGTH: usize = 2000;
+/// Longest external key or link we accept, counted in characters.
+pub const REFERENCE_MAX_LENGTH: usize = 500;
+/// Largest actual effort we accept, which is far beyond any real story.
+pub const ACTUAL_MAX: f64 = 10_000.0;
+
+/// Something on a breakout's backlog for the team to estimate.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Story {
+    pub id: i64,
+    pub breakout_id: i64,
+    pub title: String,
+    pub position: i64,
+    pub estimate: Option<String>,
+    pub estimate_reason: Option<String>,
+    pub estimated_at: Option<chrono::NaiveDateTime>,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+    /// The issue this story came from, e.g. "PROJ-123".
+    pub external_key: Option<String>,
+    pub external_url: Option<String>,
+    pub sync_status: Option<SyncStatus>,
+    pub sync_error: Option<String>,
+    pub description: Option<String>,
+    /// The effort the story really took, in the same units as its estimate.
+    pub actual: Option<f64>,
+    pub actual_recorded_at: Option<chrono::NaiveDateTime>,
+}
+impl Story {
+    pub fn is_estimated(&self) -> bool {
+        self.estimate.is_some()
+    }
+
+    pub fn actual_label(&self) -> Option<String> {
+        self.actual.map(format_points)
+    }
+
+    pub fn is_syncing(&self) -> bool {
+        self.sync_status == Some(SyncStatus::Pending)
+    }
+
+    pub fn is_synced(&self) -> bool {
+        self.sync_status == Some(SyncStatus::Synced)
+    }
+
+    pub fn sync_failed(&self) -> bool {
+        self.sync_status == Some(SyncStatus::Failed)
+    }
+}
+
+pub struct NewStory {
+    pub breakout_id: i64,
+    pub title: String,
+}
+impl NewStory {
+    pub fn parse(breakout_id: i64, title: &str) -> Result<Self, AppError> {
+        let title = normalize_line(title);
+
+        if title.is_empty() {
+            return Err(AppError::Validation("Please enter a story.".to_string()));
+        }
+
+        if has_control_chars(&title) {
+            return Err(AppError::Validation(
+                "Stories can't contain control characters.".to_string(),
+            ));
+        }
+
+        if title.chars().count() > STORY_MAX_LENGTH {
+            return Err(AppError::Validation(format!(
+                "Stories can be at most {STORY_MAX_LENGTH} characters."
+            )));
+        }
+
+        Ok(Self { breakout_id, title })
+    }
+
+    /// A story for an issue pulled in from a tracker. Rather than turning
+    /// the issue away, its title is made to fit: control characters are
+    /// dropped, a long one is cut short, and a missing one is replaced by
+    /// the issue's key.
+    pub fn imported(breakout_id: i64, issue: &ImportedIssue) -> Result<Self, AppError> {
+        let title: String = normalize_line(&issue.title)
+            .chars()
+            .filter(|c| !c.is_control())
+            .collect();
+        let title = match title.chars().count() > STORY_MAX_LENGTH {
+            true => {
+                let cut: String = title.chars().take(STORY_MAX_LENGTH - 1).collect();
+                format!("{}…", cut.trim_end())
+            }
+            false => title,
+        };
+
+        match title.is_empty() {
+            true => Self::parse(breakout_id, &issue.key),
+            false => Self::parse(breakout_id, &title),
+        }
+    }
+}
+
+/// A story pushed onto the backlog by another tool.
+#[derive(Clone)]
+pub struct IncomingStory {
+    pub breakout_id: i64,
+    pub title: String,
+    pub link: Option<String>,
+    pub description: Option<String>,
+    /// Identifies the story in the other tool, so sending it twice doesn't
+    /// add it twice.
+    pub external_key: Option<String>,
+}
+impl IncomingStory {
+    pub fn validated(mut self) -> Result<Self, AppError> {
+        self.title = NewStory::parse(self.breakout_id, &self.title)?.title;
+        self.link = self
+            .link
+            .map(|l| normalize_line(&l))
+            .filter(|l| !l.is_empty());
+        self.description = self
+            .description
+            .map(|d| normalize_text(&d))
+            .filter(|d| !d.is_empty());
+        self.external_key = self
+            .external_key
+            .map(|k| normalize_line(&k))
+            .filter(|k| !k.is_empty());
+
+        if [&self.link, &self.description, &self.external_key]
+            .iter()
+            .any(|value| value.as_deref().is_some_and(has_control_chars))
+        {
+            return Err(AppError::Validation(
+                "Stories can't contain control characters.".to_string(),
+            ));
+        }
+
+        if self
+            .description
+            .as_ref()
+            .is_some_and(|d| d.chars().count() > DESCRIPTION_MAX_LENGTH)
+        {
+            return Err(AppError::Validation(format!(
+                "Descriptions can be at most {DESCRIPTION_MAX_LENGTH} characters."
+            )));
+        }
+
+        if [&self.link, &self.external_key].iter().any(|value| {
+            value
+                .as_ref()
+                .is_some_and(|v| v.chars().count() > REFERENCE_MAX_LENGTH)
+        }) {
+            return Err(AppError::Validation(format!(
+                "Links and external keys can be at most {REFERENCE_MAX_LENGTH} characters."
+            )));
+        }
+
+        if self
+            .link
+            .as_ref()
+            .is_some_and(|l| !l.starts_with("https://"))
+        {
+            return Err(AppError::Validation(
+                "Links should start with https://.".to_string(),
+            ));
+        }
+
+        Ok(self)
+    }
+}
+
+/// The facilitator's final call on a story.
+#[derive(Clone)]
+pub struct AcceptEstimate {
+    pub story_id: i64,
+    pub estimate: String,
+    pub reason: String,
+    /// What the votes suggested, which needs no reason to accept.
+    pub suggested: Option<String>,
+}
+impl AcceptEstimate {
+    /// Makes sure there is an estimate, and a reason when it differs from
+    /// what the votes suggested.
+    pub fn validated(mut self) -> Result<Self, AppError> {
+        self.estimate = normalize_line(&self.estimate);
+        self.reason = normalize_text(&self.reason);
+
+        if self.estimate.is_empty() {
+            return Err(AppError::Validation(
+                "Please enter an estimate.".to_string(),
+            ));
+        }
+
+        if has_control_chars(&self.estimate) || has_control_chars(&self.reason) {
+            return Err(AppError::Validation(
+                "Estimates and reasons can't contain control characters.".to_string(),
+            ));
+        }
+
+        if self.estimate.chars().count() > ESTIMATE_MAX_LENGTH {
+            return Err(AppError::Validation(format!(
+                "Estimates can be at most {ESTIMATE_MAX_LENGTH} characters."
+            )));
+        }
+
+        if self.reason.chars().count() > REASON_MAX_LENGTH {
+            return Err(AppError::Validation(format!(
+                "Reasons can be at most {REASON_MAX_LENGTH} characters."
+            )));
+        }
+
+        if self.suggested.as_ref() != Some(&self.estimate) && self.reason.is_empty() {
+            return Err(AppError::Validation(
+                "Please say why you're going with a different estimate.".to_string(),
+            ));
+        }
+
+        Ok(self)
+    }
+}
+
+/// The effort a story really took, or `None` to clear it.
+pub struct RecordActual {
+    pub story_id: i64,
+    pub actual: Option<f64>,
+}
+impl RecordActual {
+    pub fn parse(story_id: i64, actual: &str) -> Result<Self, AppError> {
+        let actual = normalize_line(actual);
+        if actual.is_empty() {
+            return Ok(Self {
+                story_id,
+                actual: None,
+            });
+        }
+
+        match actual
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite() && *value >= 0.0)
+        {
+            Some(value) if value > ACTUAL_MAX => Err(AppError::Validation(format!(
+                "Actuals can be at most {ACTUAL_MAX}."
+            ))),
+            Some(value) => Ok(Self {
+                story_id,
+                actual: Some(value),
+            }),
+            None => Err(AppError::Validation(
+                "Please enter the actual effort as a number, like 3 or 2.5.".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(key: &str, title: &str) -> ImportedIssue {
+        ImportedIssue {
+            key: key.to_string(),
+            title: title.to_string(),
+            url: format!("https://acme.atlassian.net/browse/{key}"),
+        }
+    }
+
+    #[test]
+    fn imported_titles_are_cleaned_up() {
+        let story = NewStory::imported(1, &issue("PROJ-1", "  Sign\u{7}\tin\n again ")).unwrap();
+
+        assert_eq!(story.title, "Sign in again");
+    }
+
+    #[test]
+    fn long_imported_titles_are_cut_short() {
+        let story = NewStory::imported(1, &issue("PROJ-1", &"word ".repeat(100))).unwrap();
+
+        assert_eq!(story.title.chars().count(), STORY_MAX_LENGTH);
+        assert!(story.title.ends_with("word…"), "{}", story.title);
+    }
+
+    #[test]
+    fn untitled_issues_are_named_after_their_key() {
+        let story = NewStory::imported(1, &issue("PROJ-1", "\u{0}")).unwrap();
+
+        assert_eq!(story.title, "PROJ-1");
+        assert!(NewStory::imported(1, &issue("", " ")).is_err());
+    }
+
+    fn accept(
+        estimate: &str,
+        reason: &str,
+        suggested: Option<&str>,
+    ) -> Result<AcceptEstimate, AppError> {
+        AcceptEstimate {
+            story_id: 1,
+            estimate: estimate.to_string(),
+            reason: reason.to_string(),
+            suggested: suggested.map(str::to_string),
+        }
+        .validated()
+    }
+
+    #[test]
+    fn the_suggested_estimate_needs_no_reason() {
+        let accepted = accept(" 5 ", "  ", Some("5")).unwrap();
+
+        assert_eq!(accepted.estimate, "5");
+        assert_eq!(accepted.reason, "");
+    }
+
+    #[test]
+    fn a_different_estimate_needs_a_reason() {
+        assert!(accept("8", "", Some("5")).is_err());
+        assert!(accept("8", "", None).is_err());
+
+        let accepted = accept("8", " Needs a migration ", Some("5")).unwrap();
+        assert_eq!(accepted.reason, "Needs a migration");
+    }
+
+    #[test]
+    fn estimates_and_reasons_are_checked() {
+        let too_long = "9".repeat(ESTIMATE_MAX_LENGTH + 1);
+        let long_reason = "x".repeat(REASON_MAX_LENGTH + 1);
+
+        assert!(accept("", "Just because", None).is_err());
+        assert!(accept(&too_long, "Just because", None).is_err());
+        assert!(accept("8", &long_reason, None).is_err());
+        assert!(accept("8", "Just\u{7} because", None).is_err());
+    }
+
+    fn incoming(link: &str) -> Result<IncomingStory, AppError> {
+        IncomingStory {
+            breakout_id: 1,
+            title: "Sign in".to_string(),
+            link: Some(link.to_string()),
+            description: None,
+            external_key: None,
+        }
+        .validated()
+    }
+
+    #[test]
+    fn incoming_links_must_be_https() {
+        assert_eq!(
+            incoming(" https://acme.atlassian.net/browse/PROJ-1 ")
+                .unwrap()
+                .link
+                .as_deref(),
+            Some("https://acme.atlassian.net/browse/PROJ-1")
+        );
+        assert_eq!(incoming("  ").unwrap().link, None);
+        assert!(incoming("http://acme.atlassian.net/browse/PROJ-1").is_err());
+        assert!(incoming("javascript:alert(1)").is_err());
+    }
+
+    #[test]
+    fn actuals_are_numbers_within_bounds() {
+        let actual = |value: &str| RecordActual::parse(1, value).map(|r| r.actual);
+
+        assert_eq!(actual(" 2.5 ").unwrap(), Some(2.5));
+        assert_eq!(actual("0").unwrap(), Some(0.0));
+        assert_eq!(actual(&ACTUAL_MAX.to_string()).unwrap(), Some(ACTUAL_MAX));
+        assert_eq!(actual("  ").unwrap(), None);
+
+        for value in ["10000.5", "-1", "NaN", "inf", "a lot"] {
+            assert!(actual(value).is_err(), "{value}");
+        }
+    }
+}
@@ -0,0 +1,379 @@
+use crate::{
+    domain::{
+        breakout::Breakout,
+        deck::{self, Card},
+        round_stats::RoundStats,
+        session_report::{SavedSession, SessionEstimate, SessionRound, SessionVote, format_points},
+    },
+    error::AppError,
+    util::{
+        chart::Chart,
+        text::{has_control_chars, normalize_line},
+    },
+};
+
+/// Longest team name we accept, counted in characters.
+pub const TEAM_NAME_MAX_LENGTH: usize = 50;
+/// How many of a team's latest sessions its history goes back.
+pub const HISTORY_LENGTH: i64 = 30;
+
+/// A group of breakouts whose sessions are looked at together.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Team {
+    pub id: i64,
+    pub lookup_id: String,
+    pub name: String,
+    pub created_by: Option<i64>,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+pub struct NewTeam {
+    pub lookup_id: String,
+    pub name: String,
+    pub created_by: Option<i64>,
+}
+impl NewTeam {
+    pub fn named(name: &str, created_by: Option<i64>) -> Result<Self, AppError> {
+        let name = normalize_line(name);
+
+        if has_control_chars(&name) {
+            return Err(AppError::Validation(
+                "Team names can't contain control characters.".to_string(),
+            ));
+        }
+
+        if name.is_empty() {
+            return Err(AppError::Validation(
+                "Please give the team a name.".to_string(),
+            ));
+        }
+
+        if name.chars().count() > TEAM_NAME_MAX_LENGTH {
+            return Err(AppError::Validation(format!(
+                "Team names can be at most {TEAM_NAME_MAX_LENGTH} characters."
+            )));
+        }
+
+        Ok(Self {
+            lookup_id: uuid::Uuid::new_v4().to_string(),
+            name,
+            created_by,
+        })
+    }
+}
+
+/// How one session went, as a point in the team's history.
+pub struct SessionTrend {
+    pub session: SavedSession,
+    pub breakout_lookup_id: String,
+    pub breakout_title: String,
+    pub points: f64,
+    pub estimated: usize,
+    pub rounds: usize,
+    /// The share of stories voted on that needed more than one round.
+    pub revote_rate: Option<f64>,
+    /// The share of rounds where everyone who voted agreed.
+    pub consensus_rate: Option<f64>,
+}
+impl SessionTrend {
+    pub fn points_label(&self) -> String {
+        format_points(self.points)
+    }
+
+    pub fn revote_label(&self) -> String {
+        percent_label(self.revote_rate)
+    }
+
+    pub fn consensus_label(&self) -> String {
+        percent_label(self.consensus_rate)
+    }
+
+    fn chart_label(&self) -> String {
+        self.session.started_at.format("%b %-d").to_string()
+    }
+}
+
+/// The team's latest sessions, oldest first, along with every estimate
+/// they accepted.
+pub struct TeamHistory {
+    pub sessions: Vec<SessionTrend>,
+    pub estimates: Vec<SessionEstimate>,
+}
+impl TeamHistory {
+    pub fn new(
+        breakouts: &[Breakout],
+        mut sessions: Vec<SavedSession>,
+        rounds: Vec<SessionRound>,
+        votes: Vec<SessionVote>,
+        estimates: Vec<SessionEstimate>,
+    ) -> Self {
+        sessions.sort_by_key(|s| s.started_at);
+
+        let sessions = sessions
+            .into_iter()
+            .map(|session| {
+                let breakout = breakouts.iter().find(|b| b.id == session.breakout_id);
+                let rounds: Vec<&SessionRound> = rounds
+                    .iter()
+                    .filter(|r| r.session_id == session.id)
+                    .collect();
+                let accepted: Vec<&SessionEstimate> = estimates
+                    .iter()
+                    .filter(|e| e.session_id == session.id)
+                    .collect();
+
+                let mut stories: Vec<(i64, usize)> = vec![];
+                for story_id in rounds.iter().filter_map(|r| r.story_id) {
+                    match stories.iter_mut().find(|(id, _)| *id == story_id) {
+                        Some((_, count)) => *count += 1,
+                        None => stories.push((story_id, 1)),
+                    }
+                }
+                let consensus = rounds
+                    .iter()
+                    .filter(|round| {
+                        RoundStats::new(
+                            votes
+                                .iter()
+                                .filter(|v| v.round_id == round.id)
+                                .map(|v| v.vote.as_deref()),
+                        )
+                        .consensus
+                    })
+                    .count();
+
+                SessionTrend {
+                    breakout_lookup_id: breakout.map(|b| b.lookup_id.clone()).unwrap_or_default(),
+                    breakout_title: breakout.map(|b| b.title.clone()).unwrap_or_default(),
+                    points: accepted
+                        .iter()
+                        .filter_map(|e| match Card::parse(&e.estimate) {
+                            Some(Card::Number(n)) => Some(n),
+                            _ => None,
+                        })
+                        .sum(),
+                    estimated: accepted.len(),
+                    rounds: rounds.len(),
+                    revote_rate: rate(
+                        stories.iter().filter(|(_, count)| *count > 1).count(),
+                        stories.len(),
+                    ),
+                    consensus_rate: rate(consensus, rounds.len()),
+                    session,
+                }
+            })
+            .collect();
+
+        Self {
+            sessions,
+            estimates,
+        }
+    }
+
+    pub fn charts(&self) -> Vec<Chart> {
+        let labels: Vec<String> = self.sessions.iter().map(|s| s.chart_label()).collect();
+        let series = |value: fn(&SessionTrend) -> Option<f64>| -> Vec<(String, Option<f64>)> {
+            labels
+                .iter()
+                .cloned()
+                .zip(self.sessions.iter().map(value))
+                .collect()
+        };
+
+        let points: Vec<(String, f64)> = labels
+            .iter()
+            .cloned()
+            .zip(self.sessions.iter().map(|s| s.points))
+            .collect();
+        let distribution: Vec<(String, f64)> =
+            deck::tally(self.estimates.iter().map(|e| e.estimate.as_str()))
+                .into_iter()
+                .map(|(card, count)| (card.value(), count as f64))
+                .collect();
+
+        vec![
+            Chart::bars(
+                "Points per Session",
+                "The sum of every numeric estimate accepted in each session.",
+                &points,
+                "",
+            ),
+            Chart::line(
+                "Re-votes",
+                "How many of the stories voted on needed more than one round.",
+                &series(|s| s.revote_rate.map(|r| r * 100.0)),
+                Some(100.0),
+                "%",
+            ),
+            Chart::line(
+                "Consensus",
+                "How many rounds ended with everyone who voted agreeing.",
+                &series(|s| s.consensus_rate.map(|r| r * 100.0)),
+                Some(100.0),
+                "%",
+            ),
+            Chart::bars(
+                "Estimates",
+                "How often each estimate was accepted across these sessions.",
+                &distribution,
+                "",
+            ),
+        ]
+    }
+
+    pub fn total_points(&self) -> String {
+        format_points(self.sessions.iter().map(|s| s.points).sum())
+    }
+
+    /// The average points per session, which is what most teams mean by
+    /// velocity.
+    pub fn velocity(&self) -> Option<String> {
+        match self.sessions.len() {
+            0 => None,
+            n => Some(format_points(
+                self.sessions.iter().map(|s| s.points).sum::<f64>() / n as f64,
+            )),
+        }
+    }
+}
+
+fn rate(part: usize, whole: usize) -> Option<f64> {
+    match whole {
+        0 => None,
+        whole => Some(part as f64 / whole as f64),
+    }
+}
+
+fn percent_label(rate: Option<f64>) -> String {
+    match rate {
+        Some(rate) => format!("{:.0}%", rate * 100.0),
+        None => "—".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, NaiveDateTime, TimeDelta};
+
+    fn day(n: i64) -> NaiveDateTime {
+        DateTime::from_timestamp(1_700_000_000, 0)
+            .unwrap()
+            .naive_utc()
+            + TimeDelta::days(n)
+    }
+
+    fn session(id: i64, day_number: i64) -> SavedSession {
+        SavedSession {
+            id,
+            breakout_id: 1,
+            started_at: day(day_number),
+            ended_at: day(day_number) + TimeDelta::hours(1),
+            created_at: day(day_number),
+        }
+    }
+
+    fn round(id: i64, session_id: i64, story_id: Option<i64>) -> SessionRound {
+        SessionRound {
+            id,
+            session_id,
+            story_id,
+            story_title: None,
+            external_key: None,
+            started_at: day(0),
+            revealed_at: day(0),
+        }
+    }
+
+    fn votes(round_id: i64, values: &[&str]) -> impl Iterator<Item = SessionVote> {
+        values.iter().map(move |value| SessionVote {
+            id: 0,
+            round_id,
+            name: "Ann".to_string(),
+            vote: Some(value.to_string()),
+        })
+    }
+
+    fn estimate(session_id: i64, estimate: &str) -> SessionEstimate {
+        SessionEstimate {
+            id: 0,
+            session_id,
+            story_id: None,
+            story_title: "Sign in".to_string(),
+            external_key: None,
+            external_url: None,
+            estimate: estimate.to_string(),
+            reason: None,
+            accepted_at: day(0),
+        }
+    }
+
+    /// A session that voted twice on one story and once on another, and a
+    /// quiet one before it that didn't vote at all.
+    fn history() -> TeamHistory {
+        TeamHistory::new(
+            &[],
+            vec![session(2, 7), session(1, 0)],
+            vec![
+                round(1, 2, Some(1)),
+                round(2, 2, Some(1)),
+                round(3, 2, Some(2)),
+                round(4, 2, None),
+            ],
+            votes(1, &["3", "8"])
+                .chain(votes(2, &["5", "5"]))
+                .chain(votes(3, &["?", "?"]))
+                .collect(),
+            vec![estimate(2, "5"), estimate(2, "3"), estimate(2, "?")],
+        )
+    }
+
+    #[test]
+    fn works_out_consensus_and_revote_rates() {
+        let history = history();
+        let busy = &history.sessions[1];
+
+        assert_eq!(busy.session.id, 2);
+        assert_eq!(busy.rounds, 4);
+        assert_eq!(busy.revote_rate, Some(0.5));
+        assert_eq!(busy.consensus_rate, Some(0.25));
+        assert_eq!(busy.revote_label(), "50%");
+        assert_eq!(busy.consensus_label(), "25%");
+    }
+
+    #[test]
+    fn has_no_rates_without_rounds() {
+        let history = history();
+        let quiet = &history.sessions[0];
+
+        assert_eq!(quiet.session.id, 1);
+        assert_eq!(quiet.revote_rate, None);
+        assert_eq!(quiet.consensus_rate, None);
+        assert_eq!(quiet.consensus_label(), "—");
+    }
+
+    #[test]
+    fn velocity_is_the_average_per_session() {
+        let history = history();
+
+        assert_eq!(history.sessions[1].points_label(), "8");
+        assert_eq!(history.sessions[1].estimated, 3);
+        assert_eq!(history.total_points(), "8");
+        assert_eq!(history.velocity().as_deref(), Some("4"));
+        assert_eq!(
+            TeamHistory::new(&[], vec![], vec![], vec![], vec![]).velocity(),
+            None
+        );
+    }
+
+    #[test]
+    fn team_names_are_checked() {
+        assert_eq!(
+            NewTeam::named("  Platform  ", None).unwrap().name,
+            "Platform"
+        );
+        assert!(NewTeam::named(" ", None).is_err());
+        assert!(NewTeam::named(&"x".repeat(TEAM_NAME_MAX_LENGTH + 1), None).is_err());
+    }
+}
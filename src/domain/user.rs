@@ -1,38 +1,93 @@
+use crate::{
+    domain::membership::ParticipantRole,
+    error::AppError,
+    util::text::{has_control_chars, has_format_chars, normalize_line},
+};
+use sha2::{Digest, Sha256};
+
+/// Longest display name we accept, counted in characters.
+pub const DISPLAY_NAME_MAX_LENGTH: usize = 50;
+
 #[derive(sqlx::FromRow)]
 pub struct UserRow {
     pub id: i64,
     pub lookup_id: String,
+    pub public_id: String,
     pub display_name: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct User {
     pub id: i64,
+    /// Secret; this is what the identity cookie carries.
     pub lookup_id: String,
+    /// Safe to share with other participants.
+    pub public_id: String,
     pub display_name: String,
     pub vote: Option<String>,
+    pub role: ParticipantRole,
+}
+impl User {
+    pub fn is_observer(&self) -> bool {
+        self.role == ParticipantRole::Observer
+    }
+
+    pub fn is_guest(&self) -> bool {
+        self.id == 0
+    }
+}
+/// A guest who hasn't joined a breakout yet. Nothing is stored for them
+/// until they do, so they have no id; 0 never matches a row.
+impl From<NewUser> for User {
+    fn from(user: NewUser) -> Self {
+        Self {
+            id: 0,
+            lookup_id: user.lookup_id,
+            public_id: user.public_id,
+            display_name: "Guest".to_string(),
+            vote: None,
+            role: ParticipantRole::default(),
+        }
+    }
 }
 impl From<UserRow> for User {
     fn from(row: UserRow) -> Self {
         Self {
             id: row.id,
             lookup_id: row.lookup_id,
+            public_id: row.public_id,
             display_name: row.display_name,
             vote: None,
+            role: ParticipantRole::default(),
         }
     }
 }
 
 pub struct NewUser {
     pub lookup_id: String,
+    pub public_id: String,
 }
-impl Default for NewUser {
-    fn default() -> Self {
+impl NewUser {
+    /// The public id is derived from the lookup id, so a guest keeps the
+    /// same one from their first page view to when they are stored, without
+    /// it giving the secret away.
+    pub fn new(lookup_id: String) -> Self {
+        let public_id = Sha256::digest(lookup_id.as_bytes())[..16]
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+
         Self {
-            lookup_id: uuid::Uuid::new_v4().to_string(),
+            lookup_id,
+            public_id,
         }
     }
 }
+impl Default for NewUser {
+    fn default() -> Self {
+        Self::new(uuid::Uuid::new_v4().to_string())
+    }
+}
 
 pub struct UpdateUser {
     pub id: i64,
@@ -48,3 +103,42 @@ impl From<&User> for UpdateUser {
         }
     }
 }
+
+/// A display name that has been normalized and checked, so it is safe to
+/// store and show to the rest of the breakout.
+pub struct DisplayName(String);
+impl DisplayName {
+    pub fn parse(input: &str) -> Result<Self, AppError> {
+        let name = normalize_line(input);
+
+        if has_control_chars(&name) {
+            return Err(AppError::Validation(
+                "Display names can't contain control characters.".to_string(),
+            ));
+        }
+
+        if has_format_chars(&name) {
+            return Err(AppError::Validation(
+                "Display names can't contain invisible formatting characters.".to_string(),
+            ));
+        }
+
+        if name.is_empty() {
+            return Err(AppError::Validation(
+                "Please enter a display name.".to_string(),
+            ));
+        }
+
+        if name.chars().count() > DISPLAY_NAME_MAX_LENGTH {
+            return Err(AppError::Validation(format!(
+                "Display names can be at most {DISPLAY_NAME_MAX_LENGTH} characters."
+            )));
+        }
+
+        Ok(Self(name))
+    }
+
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
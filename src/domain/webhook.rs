@@ -0,0 +1,150 @@
+use crate::{
+    domain::room_event::RoomEventKind,
+    error::AppError,
+    util::{
+        text::{has_control_chars, normalize_line},
+        time::time_ago,
+        url::parse_public_url,
+    },
+};
+
+/// Most webhooks a single breakout can have.
+pub const WEBHOOK_LIMIT: usize = 5;
+/// Longest webhook URL we accept, counted in characters.
+pub const URL_MAX_LENGTH: usize = 500;
+
+/// Somewhere a breakout's events are posted to.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Webhook {
+    pub id: i64,
+    pub breakout_id: i64,
+    pub url: String,
+    /// Encrypted; see `CredentialCipher`.
+    pub secret: String,
+    pub on_round_revealed: bool,
+    pub on_estimate_accepted: bool,
+    pub on_session_ended: bool,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+impl Webhook {
+    pub fn wants(&self, event: &RoomEventKind) -> bool {
+        match event {
+            RoomEventKind::SessionStarted(_) => false,
+            RoomEventKind::RoundRevealed(_) => self.on_round_revealed,
+            RoomEventKind::EstimateAccepted(_) => self.on_estimate_accepted,
+            RoomEventKind::SessionEnded(_) => self.on_session_ended,
+        }
+    }
+
+    /// The events this webhook receives, for showing in a list.
+    pub fn events_label(&self) -> String {
+        [
+            (self.on_round_revealed, "round.revealed"),
+            (self.on_estimate_accepted, "estimate.accepted"),
+            (self.on_session_ended, "session.ended"),
+        ]
+        .iter()
+        .filter(|(wanted, _)| *wanted)
+        .map(|(_, name)| *name)
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+}
+
+#[derive(Clone)]
+pub struct NewWebhook {
+    pub breakout_id: i64,
+    pub url: String,
+    pub on_round_revealed: bool,
+    pub on_estimate_accepted: bool,
+    pub on_session_ended: bool,
+}
+impl Default for NewWebhook {
+    fn default() -> Self {
+        Self {
+            breakout_id: 0,
+            url: String::new(),
+            on_round_revealed: true,
+            on_estimate_accepted: true,
+            on_session_ended: true,
+        }
+    }
+}
+impl NewWebhook {
+    pub fn validated(mut self) -> Result<Self, AppError> {
+        self.url = normalize_line(&self.url);
+
+        if has_control_chars(&self.url) {
+            return Err(AppError::Validation(
+                "Webhook URLs can't contain control characters.".to_string(),
+            ));
+        }
+
+        if self.url.chars().count() > URL_MAX_LENGTH {
+            return Err(AppError::Validation(format!(
+                "Webhook URLs can be at most {URL_MAX_LENGTH} characters."
+            )));
+        }
+
+        if let Err(message) = parse_public_url(&self.url) {
+            return Err(AppError::Validation(message.to_string()));
+        }
+
+        if !(self.on_round_revealed || self.on_estimate_accepted || self.on_session_ended) {
+            return Err(AppError::Validation(
+                "Please pick at least one event to send.".to_string(),
+            ));
+        }
+
+        Ok(self)
+    }
+}
+
+/// Where a delivery is in its life.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+pub enum DeliveryStatus {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+/// One event on its way to one webhook.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub webhook_id: i64,
+    pub delivery_id: String,
+    pub event: String,
+    pub payload: String,
+    pub status: DeliveryStatus,
+    pub attempts: i64,
+    pub next_attempt_at: chrono::NaiveDateTime,
+    /// The HTTP status of the last attempt, if it got that far.
+    pub response_status: Option<i64>,
+    pub error: Option<String>,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+impl WebhookDelivery {
+    pub fn is_pending(&self) -> bool {
+        self.status == DeliveryStatus::Pending
+    }
+
+    pub fn is_delivered(&self) -> bool {
+        self.status == DeliveryStatus::Delivered
+    }
+
+    pub fn last_activity(&self) -> String {
+        time_ago(&self.updated_at)
+    }
+}
+
+/// A delivery along with where it went, for the delivery log.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DeliveryLogEntry {
+    #[sqlx(flatten)]
+    pub delivery: WebhookDelivery,
+    pub url: String,
+}
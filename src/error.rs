@@ -0,0 +1,79 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use log::{error, warn};
+use std::fmt::Display;
+
+/// Every way a request can fail. The response only carries the status and
+/// the error itself; `middleware::errors` turns it into a page or an htmx
+/// fragment once it knows who asked.
+#[derive(Debug, Clone)]
+pub enum AppError {
+    NotFound(String),
+    Forbidden(String),
+    Validation(String),
+    /// The message is for the logs only; users get a generic apology.
+    Internal(String),
+}
+impl AppError {
+    pub fn internal(context: &str, error: impl Display) -> Self {
+        Self::Internal(format!("{context}: {error}"))
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::Forbidden(_) => StatusCode::FORBIDDEN,
+            Self::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::NotFound(_) => "Not Found",
+            Self::Forbidden(_) => "Forbidden",
+            Self::Validation(_) => "Invalid Request",
+            Self::Internal(_) => "Something Went Wrong",
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            Self::NotFound(message) | Self::Forbidden(message) | Self::Validation(message) => {
+                message
+            }
+            Self::Internal(_) => "We ran into a problem on our end. Please try again.",
+        }
+    }
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(error: sqlx::Error) -> Self {
+        match error {
+            sqlx::Error::RowNotFound => Self::NotFound("We couldn't find that.".to_string()),
+            error => Self::internal("database error", error),
+        }
+    }
+}
+
+impl From<askama::Error> for AppError {
+    fn from(error: askama::Error) -> Self {
+        Self::internal("failed to render template", error)
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        match &self {
+            Self::Internal(context) => error!("{context}"),
+            Self::Forbidden(message) => warn!("Forbidden: {message}"),
+            Self::NotFound(_) | Self::Validation(_) => {}
+        }
+
+        let mut response = self.status().into_response();
+        response.extensions_mut().insert(self);
+        response
+    }
+}
@@ -1,15 +1,14 @@
-use axum::response::IntoResponse;
-use axum::response::Redirect;
-use axum::response::Response;
 use axum::{extract::FromRequestParts, http::request::Parts};
+use std::collections::HashMap;
 
 use crate::SharedState;
 use crate::domain::breakout::Breakout;
+use crate::error::AppError;
 
 pub struct BreakoutRoom(pub Breakout);
 
 impl FromRequestParts<SharedState> for BreakoutRoom {
-    type Rejection = Response;
+    type Rejection = AppError;
 
     async fn from_request_parts(
         parts: &mut Parts,
@@ -17,14 +16,20 @@ impl FromRequestParts<SharedState> for BreakoutRoom {
     ) -> Result<Self, Self::Rejection> {
         use axum::extract::Path;
 
-        let lookup_id = match Path::<String>::from_request_parts(parts, state).await {
-            Ok(Path(id)) => id,
-            Err(_) => return Err(Redirect::to("/").into_response()),
+        let not_found = || AppError::NotFound("This breakout doesn't exist.".to_string());
+
+        let lookup_id = match Path::<HashMap<String, String>>::from_request_parts(parts, state)
+            .await
+            .map(|Path(mut params)| params.remove("lookup_id"))
+        {
+            Ok(Some(id)) => id,
+            _ => return Err(not_found()),
         };
 
         match state.breakout_service.find_by_lookup_id(lookup_id).await {
             Ok(breakout) => Ok(BreakoutRoom(breakout)),
-            Err(_) => Err(Redirect::to("/").into_response()),
+            Err(sqlx::Error::RowNotFound) => Err(not_found()),
+            Err(e) => Err(AppError::internal("failed to find breakout", e)),
         }
     }
 }
@@ -1,28 +1,36 @@
-use axum::response::IntoResponse;
-use axum::response::Response;
-use axum::{extract::FromRequestParts, http::request::Parts, response::Redirect};
+use axum::{extract::FromRequestParts, http::request::Parts};
 
-use crate::{SharedState, domain::user::User, extract::BaseUser};
+use crate::{
+    SharedState,
+    domain::user::{NewUser, User},
+    error::AppError,
+    extract::BaseUser,
+};
 
 #[derive(Clone, PartialEq, Eq)]
 pub struct BreakoutUser(pub User);
 
 impl FromRequestParts<SharedState> for BreakoutUser {
-    type Rejection = Response;
+    type Rejection = AppError;
 
     async fn from_request_parts(
         parts: &mut Parts,
         state: &SharedState,
     ) -> Result<Self, Self::Rejection> {
-        // TODO: if a person doesn't exist, we need to create them,
-        // or at the very least handle gracefully.
         let user = BaseUser::from_request_parts(parts, state)
             .await
-            .map_err(|_| Redirect::to("/").into_response())?;
+            .map_err(|status| AppError::internal("failed to identify user", status))?;
 
         let user = match user {
             BaseUser::User(user) => BreakoutUser(user),
-            _ => return Err(Redirect::to("/").into_response()),
+            // Guests aren't stored until they join, so just looking at a
+            // breakout leaves nothing behind.
+            BaseUser::Guest(lookup_id) => BreakoutUser(User::from(NewUser::new(lookup_id))),
+            BaseUser::None => {
+                return Err(AppError::Internal(
+                    "could not identify the user".to_string(),
+                ));
+            }
         };
 
         Ok(user)
@@ -1,9 +1,8 @@
 use axum::extract::FromRef;
 use axum::{extract::FromRequestParts, http::request::Parts};
-use axum_extra::extract::CookieJar;
 use std::sync::Arc;
 
-use crate::domain::user::NewUser;
+use crate::middleware::identity::Whoami;
 use crate::{SharedState, domain::user::User};
 
 pub mod breakout;
@@ -11,6 +10,9 @@ pub mod breakout_user;
 
 pub enum BaseUser {
     User(User),
+    /// Someone we have handed an identity to, but who has not joined a
+    /// breakout yet, so there is nothing stored for them.
+    Guest(String),
     None,
 }
 
@@ -22,21 +24,15 @@ impl FromRequestParts<SharedState> for BaseUser {
         state: &SharedState,
     ) -> Result<Self, Self::Rejection> {
         let state = Arc::from_ref(state);
-        let jar = CookieJar::from_headers(&parts.headers);
 
-        let whoami = match jar.get("whoami") {
-            Some(cookie) => cookie.value().to_string(),
-            _ => {
-                // User does not exist, so create one.
-                match state.user_service.create(&NewUser::default()).await {
-                    Ok(user) => user.lookup_id,
-                    Err(_) => return Ok(BaseUser::None),
-                }
-            }
+        let whoami = match parts.extensions.get::<Whoami>() {
+            Some(Whoami(lookup_id)) => lookup_id.clone(),
+            None => return Ok(BaseUser::None),
         };
 
         match state.user_service.find_by_lookup_id(&whoami).await {
             Ok(user) => Ok(BaseUser::User(user)),
+            Err(sqlx::Error::RowNotFound) => Ok(BaseUser::Guest(whoami)),
             Err(_) => Ok(BaseUser::None),
         }
     }
@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::{Value, json};
+
+use crate::domain::{
+    breakout::Breakout,
+    notification::{Notifier, NotifierError},
+    room_event::SessionEnded,
+};
+
+/// The accent down the side of every embed.
+const COLOR: u32 = 0xdc5639;
+/// Discord rejects embeds with more fields than this.
+const MAX_FIELDS: usize = 25;
+
+/// Posts embeds to a Discord channel webhook.
+pub struct DiscordNotifier {
+    pub http: Client,
+    pub webhook_url: String,
+}
+
+#[async_trait]
+impl Notifier for DiscordNotifier {
+    async fn session_started(&self, breakout: &Breakout, url: &str) -> Result<(), NotifierError> {
+        let embed = json!({
+            "title": truncate(&breakout.title, 256),
+            "url": url,
+            "description": format!(
+                "The session is underway. Join in with the code **{}**.",
+                breakout.code
+            ),
+            "color": COLOR,
+        });
+
+        super::post(&self.http, &self.webhook_url, &json!({ "embeds": [embed] })).await
+    }
+
+    async fn session_summary(
+        &self,
+        breakout: &Breakout,
+        url: &str,
+        session: &SessionEnded,
+    ) -> Result<(), NotifierError> {
+        let mut description = super::summary_line(session);
+        if session.estimates.len() > MAX_FIELDS {
+            description.push_str(&format!(
+                " Showing the first {MAX_FIELDS} of {}.",
+                session.estimates.len()
+            ));
+        }
+
+        let fields: Vec<Value> = session
+            .estimates
+            .iter()
+            .take(MAX_FIELDS)
+            .map(|accepted| {
+                let story = &accepted.story;
+                let name = match &story.external_key {
+                    Some(key) => format!("{key} {}", story.title),
+                    None => story.title.clone(),
+                };
+                json!({
+                    "name": truncate(&name, 256),
+                    "value": truncate(&accepted.estimate, 1024),
+                    "inline": true,
+                })
+            })
+            .collect();
+        let embed = json!({
+            "title": truncate(&breakout.title, 256),
+            "url": url,
+            "description": description,
+            "color": COLOR,
+            "fields": fields,
+            "timestamp": session.ended_at,
+        });
+
+        super::post(&self.http, &self.webhook_url, &json!({ "embeds": [embed] })).await
+    }
+}
+
+/// Shortens text to Discord's limit for where it goes.
+fn truncate(text: &str, max: usize) -> String {
+    match text.chars().count() > max {
+        true => format!("{}…", text.chars().take(max - 1).collect::<String>()),
+        false => text.to_string(),
+    }
+}
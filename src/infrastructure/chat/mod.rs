@@ -0,0 +1,73 @@
+use reqwest::Client;
+use serde_json::Value;
+
+use crate::domain::{
+    breakout::Breakout,
+    notification::{Notifier, NotifierError},
+    room_event::SessionEnded,
+};
+
+pub mod discord;
+pub mod slack;
+pub mod teams;
+
+/// Builds a notifier for every chat the breakout posts to.
+pub fn notifiers(http: &Client, breakout: &Breakout) -> Vec<Box<dyn Notifier>> {
+    let mut notifiers: Vec<Box<dyn Notifier>> = vec![];
+
+    if let Some(webhook_url) = &breakout.slack_webhook_url {
+        notifiers.push(Box::new(slack::SlackNotifier {
+            http: http.clone(),
+            webhook_url: webhook_url.clone(),
+        }));
+    }
+    if let Some(webhook_url) = &breakout.teams_webhook_url {
+        notifiers.push(Box::new(teams::TeamsNotifier {
+            http: http.clone(),
+            webhook_url: webhook_url.clone(),
+        }));
+    }
+    if let Some(webhook_url) = &breakout.discord_webhook_url {
+        notifiers.push(Box::new(discord::DiscordNotifier {
+            http: http.clone(),
+            webhook_url: webhook_url.clone(),
+        }));
+    }
+
+    notifiers
+}
+
+/// Posts a message to a chat's incoming webhook.
+async fn post(http: &Client, webhook_url: &str, message: &Value) -> Result<(), NotifierError> {
+    let response = http
+        .post(webhook_url)
+        .json(message)
+        .send()
+        .await
+        .map_err(|e| NotifierError(format!("Request failed: {e}")))?;
+
+    match response.status() {
+        status if status.is_success() => Ok(()),
+        status => Err(NotifierError(format!("The chat responded with {status}."))),
+    }
+}
+
+/// How a session went in a few words, e.g. "Wrapped up after 12 minutes:
+/// 3 rounds, 2 stories estimated."
+fn summary_line(session: &SessionEnded) -> String {
+    let minutes = (session.ended_at - session.started_at).num_minutes();
+    let count = |n: usize, one: &str, many: &str| match n {
+        1 => format!("1 {one}"),
+        n => format!("{n} {many}"),
+    };
+
+    format!(
+        "Wrapped up after {}: {}, {} estimated.",
+        match minutes {
+            0 => "less than a minute".to_string(),
+            n => count(n as usize, "minute", "minutes"),
+        },
+        count(session.rounds as usize, "round", "rounds"),
+        count(session.estimates.len(), "story", "stories"),
+    )
+}
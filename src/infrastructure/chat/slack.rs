@@ -0,0 +1,164 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::{Value, json};
+
+use crate::{
+    domain::{
+        breakout::Breakout,
+        notification::{Notifier, NotifierError},
+        room_event::SessionEnded,
+    },
+    util::url::parse_public_url,
+};
+
+/// Posts to a Slack incoming webhook, using Block Kit.
+pub struct SlackNotifier {
+    pub http: Client,
+    pub webhook_url: String,
+}
+
+#[async_trait]
+impl Notifier for SlackNotifier {
+    async fn session_started(&self, breakout: &Breakout, url: &str) -> Result<(), NotifierError> {
+        let text = format!(
+            "*<{url}|{}>* is underway. Join in with the code *{}*.",
+            escape(&breakout.title),
+            breakout.code
+        );
+
+        super::post(&self.http, &self.webhook_url, &message(&text, vec![])).await
+    }
+
+    async fn session_summary(
+        &self,
+        breakout: &Breakout,
+        url: &str,
+        session: &SessionEnded,
+    ) -> Result<(), NotifierError> {
+        let text = format!(
+            "*<{url}|{}>*\n{}",
+            escape(&breakout.title),
+            super::summary_line(session)
+        );
+        let lines: Vec<String> = session
+            .estimates
+            .iter()
+            .map(|accepted| {
+                let story = &accepted.story;
+                let title = match (&story.external_url, &story.external_key) {
+                    (Some(link), Some(key)) => {
+                        format!("<{link}|{}> {}", escape(key), escape(&story.title))
+                    }
+                    (Some(link), None) => format!("<{link}|{}>", escape(&story.title)),
+                    _ => escape(&story.title),
+                };
+                format!("• {title}: *{}*", escape(&accepted.estimate))
+            })
+            .collect();
+
+        super::post(&self.http, &self.webhook_url, &message(&text, lines)).await
+    }
+}
+
+/// A message with a heading and, when there are any, a list below it.
+fn message(text: &str, lines: Vec<String>) -> Value {
+    let mut blocks = vec![json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": text },
+    })];
+    if !lines.is_empty() {
+        blocks.push(json!({
+            "type": "section",
+            "text": { "type": "mrkdwn", "text": lines.join("\n") },
+        }));
+    }
+
+    json!({ "text": text, "blocks": blocks })
+}
+
+/// Tells the channel about a breakout started with `/guess`, so people can
+/// join.
+pub fn breakout_started(breakout: &Breakout, url: &str, started_by: &str) -> Value {
+    let text = format!(
+        "{} started *{}*. Join at <{url}|{url}> or with the code *{}*.",
+        escape(started_by),
+        escape(&breakout.title),
+        breakout.code
+    );
+
+    json!({
+        "response_type": "in_channel",
+        "text": text,
+        "blocks": [
+            { "type": "section", "text": { "type": "mrkdwn", "text": text } },
+            {
+                "type": "actions",
+                "elements": [{
+                    "type": "button",
+                    "text": { "type": "plain_text", "text": "Join the breakout" },
+                    "url": url,
+                    "style": "primary",
+                }],
+            },
+        ],
+    })
+}
+
+/// The reply to `/guess`, with a link that makes whoever started the
+/// breakout its facilitator. Only they see it, and it isn't unfurled, so
+/// nothing else follows it before they do.
+pub fn breakout_ready(breakout: &Breakout, claim_url: &str) -> Value {
+    let text = format!(
+        "*{}* is ready, and you're running it. This link is just for you; others can join with the code *{}*.",
+        escape(&breakout.title),
+        breakout.code
+    );
+
+    json!({
+        "response_type": "ephemeral",
+        "text": text,
+        "unfurl_links": false,
+        "blocks": [
+            { "type": "section", "text": { "type": "mrkdwn", "text": text } },
+            {
+                "type": "actions",
+                "elements": [{
+                    "type": "button",
+                    "text": { "type": "plain_text", "text": "Run the breakout" },
+                    "url": claim_url,
+                    "style": "primary",
+                }],
+            },
+        ],
+    })
+}
+
+/// Sends another message in reply to a slash command, through the
+/// `response_url` Slack gave us with it.
+pub async fn respond(
+    http: &Client,
+    response_url: &str,
+    message: &Value,
+) -> Result<(), NotifierError> {
+    let is_slack =
+        parse_public_url(response_url).is_ok_and(|url| url.host_str() == Some("hooks.slack.com"));
+    if !is_slack {
+        return Err(NotifierError(format!(
+            "{response_url} isn't a Slack response URL."
+        )));
+    }
+
+    super::post(http, response_url, message).await
+}
+
+/// A reply only the person who ran the command sees.
+pub fn ephemeral(text: &str) -> Value {
+    json!({ "response_type": "ephemeral", "text": text })
+}
+
+/// Escapes the characters Slack treats as control sequences in mrkdwn.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
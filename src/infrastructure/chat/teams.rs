@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::{Value, json};
+
+use crate::domain::{
+    breakout::Breakout,
+    notification::{Notifier, NotifierError},
+    room_event::SessionEnded,
+};
+
+/// Posts Adaptive Cards to a Microsoft Teams incoming webhook or workflow.
+pub struct TeamsNotifier {
+    pub http: Client,
+    pub webhook_url: String,
+}
+
+#[async_trait]
+impl Notifier for TeamsNotifier {
+    async fn session_started(&self, breakout: &Breakout, url: &str) -> Result<(), NotifierError> {
+        let body = vec![
+            heading(&breakout.title),
+            text(&format!(
+                "The session is underway. Join in with the code **{}**.",
+                breakout.code
+            )),
+        ];
+
+        super::post(&self.http, &self.webhook_url, &card(body, url)).await
+    }
+
+    async fn session_summary(
+        &self,
+        breakout: &Breakout,
+        url: &str,
+        session: &SessionEnded,
+    ) -> Result<(), NotifierError> {
+        let mut body = vec![
+            heading(&breakout.title),
+            text(&super::summary_line(session)),
+        ];
+        if !session.estimates.is_empty() {
+            let facts: Vec<Value> = session
+                .estimates
+                .iter()
+                .map(|accepted| {
+                    let story = &accepted.story;
+                    let title = match &story.external_key {
+                        Some(key) => format!("{key} {}", story.title),
+                        None => story.title.clone(),
+                    };
+                    json!({ "title": title, "value": accepted.estimate })
+                })
+                .collect();
+            body.push(json!({ "type": "FactSet", "facts": facts }));
+        }
+
+        super::post(&self.http, &self.webhook_url, &card(body, url)).await
+    }
+}
+
+fn heading(title: &str) -> Value {
+    json!({
+        "type": "TextBlock",
+        "text": title,
+        "size": "Medium",
+        "weight": "Bolder",
+        "wrap": true,
+    })
+}
+
+fn text(text: &str) -> Value {
+    json!({ "type": "TextBlock", "text": text, "wrap": true })
+}
+
+/// Wraps the card body in the message envelope Teams expects, with a
+/// button that opens the breakout.
+fn card(body: Vec<Value>, url: &str) -> Value {
+    json!({
+        "type": "message",
+        "attachments": [{
+            "contentType": "application/vnd.microsoft.card.adaptive",
+            "contentUrl": null,
+            "content": {
+                "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                "type": "AdaptiveCard",
+                "version": "1.4",
+                "body": body,
+                "actions": [{
+                    "type": "Action.OpenUrl",
+                    "title": "Open the breakout",
+                    "url": url,
+                }],
+            },
+        }],
+    })
+}
@@ -1,7 +1,10 @@
 use sqlx::{SqlitePool, query_as};
 use std::sync::Arc;
 
-use crate::domain::breakout::{Breakout, NewBreakout};
+use crate::domain::{
+    breakout::{Breakout, NewBreakout, UpdateBreakout},
+    notification::UpdateNotifications,
+};
 
 pub struct BreakoutRepository {
     db: Arc<SqlitePool>,
@@ -18,10 +21,121 @@ impl BreakoutRepository {
             .await
     }
 
-    pub async fn create(&self, breakout: &NewBreakout) -> Result<Breakout, sqlx::Error> {
-        query_as(r#"INSERT INTO breakouts (lookup_id) VALUES (?) RETURNING *"#)
-            .bind(&breakout.lookup_id)
+    pub async fn find_by_code(&self, code: &str) -> Result<Breakout, sqlx::Error> {
+        query_as(r#"SELECT * FROM breakouts WHERE code = ?"#)
+            .bind(code)
+            .fetch_one(self.db.as_ref())
+            .await
+    }
+
+    pub async fn find_by_display_token(&self, token: &str) -> Result<Breakout, sqlx::Error> {
+        query_as(r#"SELECT * FROM breakouts WHERE display_token = ?"#)
+            .bind(token)
             .fetch_one(self.db.as_ref())
             .await
     }
+
+    pub async fn find_by_intake_token(&self, token: &str) -> Result<Breakout, sqlx::Error> {
+        query_as(r#"SELECT * FROM breakouts WHERE intake_token = ?"#)
+            .bind(token)
+            .fetch_one(self.db.as_ref())
+            .await
+    }
+
+    pub async fn create(&self, breakout: &NewBreakout) -> Result<Breakout, sqlx::Error> {
+        query_as(
+            r#"INSERT INTO breakouts
+                 (lookup_id, title, code, facilitator_id, display_token, intake_token,
+                  started_in_slack, facilitator_token)
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?) RETURNING *"#,
+        )
+        .bind(&breakout.lookup_id)
+        .bind(&breakout.title)
+        .bind(&breakout.code)
+        .bind(breakout.facilitator_id)
+        .bind(&breakout.display_token)
+        .bind(&breakout.intake_token)
+        .bind(breakout.started_in_slack)
+        .bind(&breakout.facilitator_token)
+        .fetch_one(self.db.as_ref())
+        .await
+    }
+
+    pub async fn update(&self, breakout: &UpdateBreakout) -> Result<Breakout, sqlx::Error> {
+        query_as(
+            r#"UPDATE breakouts
+               SET title = ?, description = ?, facilitator_only_controls = ?,
+                   auto_reveal = ?, auto_reveal_countdown = ?, timer_expiry = ?,
+                   estimation_mode = ?,
+                   updated_at = CURRENT_TIMESTAMP
+               WHERE id = ? RETURNING *"#,
+        )
+        .bind(&breakout.title)
+        .bind(&breakout.description)
+        .bind(breakout.facilitator_only_controls)
+        .bind(breakout.auto_reveal)
+        .bind(breakout.auto_reveal_countdown)
+        .bind(breakout.timer_expiry)
+        .bind(breakout.estimation_mode)
+        .bind(breakout.id)
+        .fetch_one(self.db.as_ref())
+        .await
+    }
+
+    pub async fn update_notifications(
+        &self,
+        notifications: &UpdateNotifications,
+    ) -> Result<Breakout, sqlx::Error> {
+        query_as(
+            r#"UPDATE breakouts
+               SET slack_webhook_url = ?, teams_webhook_url = ?, discord_webhook_url = ?,
+                   updated_at = CURRENT_TIMESTAMP
+               WHERE id = ? RETURNING *"#,
+        )
+        .bind(&notifications.slack_webhook_url)
+        .bind(&notifications.teams_webhook_url)
+        .bind(&notifications.discord_webhook_url)
+        .bind(notifications.id)
+        .fetch_one(self.db.as_ref())
+        .await
+    }
+
+    /// Makes the user the facilitator, as long as the breakout doesn't have
+    /// one and they hold its facilitator token, if it has one. The token is
+    /// used up. Returns the breakout when it was claimed.
+    pub async fn claim_facilitator(
+        &self,
+        id: i64,
+        user_id: i64,
+        token: Option<&str>,
+    ) -> Result<Option<Breakout>, sqlx::Error> {
+        query_as(
+            r#"UPDATE breakouts
+               SET facilitator_id = ?, facilitator_token = NULL,
+                   updated_at = CURRENT_TIMESTAMP
+               WHERE id = ? AND facilitator_id IS NULL
+               AND (facilitator_token IS NULL OR facilitator_token = ?)
+               RETURNING *"#,
+        )
+        .bind(user_id)
+        .bind(id)
+        .bind(token)
+        .fetch_optional(self.db.as_ref())
+        .await
+    }
+
+    pub async fn update_team(
+        &self,
+        id: i64,
+        team_id: Option<i64>,
+    ) -> Result<Breakout, sqlx::Error> {
+        query_as(
+            r#"UPDATE breakouts SET team_id = ?, updated_at = CURRENT_TIMESTAMP
+               WHERE id = ? RETURNING *"#,
+        )
+        .bind(team_id)
+        .bind(id)
+        .fetch_one(self.db.as_ref())
+        .await
+    }
 }
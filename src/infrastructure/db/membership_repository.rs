@@ -0,0 +1,95 @@
+use sqlx::{SqlitePool, query, query_as, query_scalar};
+use std::sync::Arc;
+
+use crate::domain::membership::{ParticipantRole, RecentBreakout};
+
+pub struct MembershipRepository {
+    db: Arc<SqlitePool>,
+}
+impl MembershipRepository {
+    pub fn new(db: &Arc<SqlitePool>) -> Self {
+        Self { db: db.clone() }
+    }
+
+    /// Records that the user is in the breakout right now, adding them as a
+    /// member the first time.
+    pub async fn touch(&self, breakout_id: i64, user_id: i64) -> Result<(), sqlx::Error> {
+        query(
+            r#"INSERT INTO breakout_members (breakout_id, user_id) VALUES (?, ?)
+               ON CONFLICT (breakout_id, user_id)
+               DO UPDATE SET last_active_at = CURRENT_TIMESTAMP"#,
+        )
+        .bind(breakout_id)
+        .bind(user_id)
+        .execute(self.db.as_ref())
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_role(
+        &self,
+        breakout_id: i64,
+        user_id: i64,
+    ) -> Result<Option<ParticipantRole>, sqlx::Error> {
+        query_scalar(r#"SELECT role FROM breakout_members WHERE breakout_id = ? AND user_id = ?"#)
+            .bind(breakout_id)
+            .bind(user_id)
+            .fetch_optional(self.db.as_ref())
+            .await
+    }
+
+    pub async fn set_role(
+        &self,
+        breakout_id: i64,
+        user_id: i64,
+        role: ParticipantRole,
+    ) -> Result<(), sqlx::Error> {
+        query(
+            r#"INSERT INTO breakout_members (breakout_id, user_id, role) VALUES (?, ?, ?)
+               ON CONFLICT (breakout_id, user_id) DO UPDATE SET role = excluded.role"#,
+        )
+        .bind(breakout_id)
+        .bind(user_id)
+        .bind(role)
+        .execute(self.db.as_ref())
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn toggle_pin(&self, breakout_id: i64, user_id: i64) -> Result<(), sqlx::Error> {
+        query(
+            r#"UPDATE breakout_members SET pinned = NOT pinned
+               WHERE breakout_id = ? AND user_id = ?"#,
+        )
+        .bind(breakout_id)
+        .bind(user_id)
+        .execute(self.db.as_ref())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Pinned breakouts first, then the ones the user was in most recently.
+    pub async fn find_recent(
+        &self,
+        user_id: i64,
+        limit: i64,
+    ) -> Result<Vec<RecentBreakout>, sqlx::Error> {
+        query_as(
+            r#"SELECT b.lookup_id, b.title, b.code, m.pinned,
+                      (SELECT MAX(last_active_at) FROM breakout_members
+                       WHERE breakout_id = b.id) AS last_activity_at
+               FROM breakout_members m
+               JOIN breakouts b ON b.id = m.breakout_id
+               WHERE m.user_id = ?
+               ORDER BY m.pinned DESC, m.last_active_at DESC
+               LIMIT ?"#,
+        )
+        .bind(user_id)
+        .bind(limit)
+        .fetch_all(self.db.as_ref())
+        .await
+    }
+}
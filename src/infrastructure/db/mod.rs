@@ -2,10 +2,22 @@ use log::info;
 use sqlx::{Connection, SqlitePool, migrate::Migrator, sqlite::SqlitePoolOptions};
 
 pub mod breakout_repository;
+pub mod membership_repository;
+pub mod session_repository;
+pub mod story_repository;
+pub mod team_repository;
+pub mod tracker_repository;
 pub mod user_repository;
+pub mod webhook_repository;
 
 pub use breakout_repository::BreakoutRepository;
+pub use membership_repository::MembershipRepository;
+pub use session_repository::SessionRepository;
+pub use story_repository::StoryRepository;
+pub use team_repository::TeamRepository;
+pub use tracker_repository::TrackerRepository;
 pub use user_repository::UserRepository;
+pub use webhook_repository::WebhookRepository;
 
 static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
 
@@ -0,0 +1,233 @@
+use sqlx::{SqlitePool, query, query_as, query_scalar};
+use std::sync::Arc;
+
+use crate::domain::session_report::{
+    SavedSession, SessionEstimate, SessionParticipant, SessionRecord, SessionRound, SessionVote,
+};
+
+/// The ids of a team's latest sessions, binding the team and how many.
+const LATEST_BY_TEAM: &str = r#"SELECT s.id FROM sessions s
+    JOIN breakouts b ON b.id = s.breakout_id
+    WHERE b.team_id = ?
+    ORDER BY s.ended_at DESC, s.id DESC
+    LIMIT ?"#;
+
+pub struct SessionRepository {
+    db: Arc<SqlitePool>,
+}
+impl SessionRepository {
+    pub fn new(db: &Arc<SqlitePool>) -> Self {
+        Self { db: db.clone() }
+    }
+
+    /// The breakout's finished sessions, latest first.
+    pub async fn find_by_breakout(
+        &self,
+        breakout_id: i64,
+    ) -> Result<Vec<SavedSession>, sqlx::Error> {
+        query_as(r#"SELECT * FROM sessions WHERE breakout_id = ? ORDER BY ended_at DESC, id DESC"#)
+            .bind(breakout_id)
+            .fetch_all(self.db.as_ref())
+            .await
+    }
+
+    /// The latest sessions across all of the team's breakouts.
+    pub async fn find_by_team(
+        &self,
+        team_id: i64,
+        limit: i64,
+    ) -> Result<Vec<SavedSession>, sqlx::Error> {
+        query_as(
+            r#"SELECT s.* FROM sessions s
+               JOIN breakouts b ON b.id = s.breakout_id
+               WHERE b.team_id = ?
+               ORDER BY s.ended_at DESC, s.id DESC
+               LIMIT ?"#,
+        )
+        .bind(team_id)
+        .bind(limit)
+        .fetch_all(self.db.as_ref())
+        .await
+    }
+
+    pub async fn find(&self, breakout_id: i64, id: i64) -> Result<SavedSession, sqlx::Error> {
+        query_as(r#"SELECT * FROM sessions WHERE id = ? AND breakout_id = ?"#)
+            .bind(id)
+            .bind(breakout_id)
+            .fetch_one(self.db.as_ref())
+            .await
+    }
+
+    /// Saves the session along with its rounds, votes, estimates and
+    /// attendance, all or nothing.
+    pub async fn create(
+        &self,
+        breakout_id: i64,
+        record: &SessionRecord,
+    ) -> Result<SavedSession, sqlx::Error> {
+        let mut tx = self.db.begin().await?;
+
+        let session: SavedSession = query_as(
+            r#"INSERT INTO sessions (breakout_id, started_at, ended_at)
+               VALUES (?, ?, ?)
+               RETURNING *"#,
+        )
+        .bind(breakout_id)
+        .bind(record.started_at.naive_utc())
+        .bind(record.ended_at.naive_utc())
+        .fetch_one(&mut *tx)
+        .await?;
+
+        for round in &record.rounds {
+            let round_id: i64 = query_scalar(
+                r#"INSERT INTO session_rounds
+                     (session_id, story_id, story_title, external_key, started_at, revealed_at)
+                   VALUES (?, ?, ?, ?, ?, ?)
+                   RETURNING id"#,
+            )
+            .bind(session.id)
+            .bind(round.story.as_ref().map(|s| s.id))
+            .bind(round.story.as_ref().map(|s| &s.title))
+            .bind(round.story.as_ref().and_then(|s| s.external_key.as_ref()))
+            .bind(round.started_at.naive_utc())
+            .bind(round.revealed_at.naive_utc())
+            .fetch_one(&mut *tx)
+            .await?;
+
+            for vote in &round.votes {
+                query(r#"INSERT INTO session_votes (round_id, name, vote) VALUES (?, ?, ?)"#)
+                    .bind(round_id)
+                    .bind(&vote.name)
+                    .bind(&vote.vote)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+
+        for accepted in &record.estimates {
+            query(
+                r#"INSERT INTO session_estimates
+                     (session_id, story_id, story_title, external_key, external_url, estimate,
+                      reason, accepted_at)
+                   VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#,
+            )
+            .bind(session.id)
+            .bind(accepted.story.id)
+            .bind(&accepted.story.title)
+            .bind(&accepted.story.external_key)
+            .bind(&accepted.story.external_url)
+            .bind(&accepted.estimate)
+            .bind(&accepted.reason)
+            .bind(accepted.accepted_at.naive_utc())
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for attendance in &record.attendance {
+            query(
+                r#"INSERT INTO session_participants
+                     (session_id, user_id, name, joined_at, seconds_present, rounds_voted)
+                   VALUES (?, ?, ?, ?, ?, ?)"#,
+            )
+            .bind(session.id)
+            .bind(attendance.user_id)
+            .bind(&attendance.name)
+            .bind(attendance.joined_at.naive_utc())
+            .bind(attendance.seconds_present)
+            .bind(attendance.rounds_voted)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(session)
+    }
+
+    pub async fn rounds(&self, session_id: i64) -> Result<Vec<SessionRound>, sqlx::Error> {
+        query_as(r#"SELECT * FROM session_rounds WHERE session_id = ? ORDER BY revealed_at, id"#)
+            .bind(session_id)
+            .fetch_all(self.db.as_ref())
+            .await
+    }
+
+    pub async fn votes(&self, session_id: i64) -> Result<Vec<SessionVote>, sqlx::Error> {
+        query_as(
+            r#"SELECT v.* FROM session_votes v
+               JOIN session_rounds r ON r.id = v.round_id
+               WHERE r.session_id = ?
+               ORDER BY v.id"#,
+        )
+        .bind(session_id)
+        .fetch_all(self.db.as_ref())
+        .await
+    }
+
+    pub async fn estimates(&self, session_id: i64) -> Result<Vec<SessionEstimate>, sqlx::Error> {
+        query_as(r#"SELECT * FROM session_estimates WHERE session_id = ? ORDER BY accepted_at, id"#)
+            .bind(session_id)
+            .fetch_all(self.db.as_ref())
+            .await
+    }
+
+    pub async fn participants(
+        &self,
+        session_id: i64,
+    ) -> Result<Vec<SessionParticipant>, sqlx::Error> {
+        query_as(
+            r#"SELECT * FROM session_participants
+               WHERE session_id = ?
+               ORDER BY seconds_present DESC, id"#,
+        )
+        .bind(session_id)
+        .fetch_all(self.db.as_ref())
+        .await
+    }
+
+    /// The rounds of the team's latest sessions, as picked by `find_by_team`.
+    pub async fn rounds_by_team(
+        &self,
+        team_id: i64,
+        limit: i64,
+    ) -> Result<Vec<SessionRound>, sqlx::Error> {
+        query_as(&format!(
+            r#"SELECT * FROM session_rounds WHERE session_id IN ({LATEST_BY_TEAM})
+               ORDER BY revealed_at, id"#
+        ))
+        .bind(team_id)
+        .bind(limit)
+        .fetch_all(self.db.as_ref())
+        .await
+    }
+
+    pub async fn votes_by_team(
+        &self,
+        team_id: i64,
+        limit: i64,
+    ) -> Result<Vec<SessionVote>, sqlx::Error> {
+        query_as(&format!(
+            r#"SELECT v.* FROM session_votes v
+               JOIN session_rounds r ON r.id = v.round_id
+               WHERE r.session_id IN ({LATEST_BY_TEAM})
+               ORDER BY v.id"#
+        ))
+        .bind(team_id)
+        .bind(limit)
+        .fetch_all(self.db.as_ref())
+        .await
+    }
+
+    pub async fn estimates_by_team(
+        &self,
+        team_id: i64,
+        limit: i64,
+    ) -> Result<Vec<SessionEstimate>, sqlx::Error> {
+        query_as(&format!(
+            r#"SELECT * FROM session_estimates WHERE session_id IN ({LATEST_BY_TEAM})
+               ORDER BY accepted_at, id"#
+        ))
+        .bind(team_id)
+        .bind(limit)
+        .fetch_all(self.db.as_ref())
+        .await
+    }
+}
@@ -0,0 +1,171 @@
+use sqlx::{SqlitePool, query, query_as};
+use std::sync::Arc;
+
+use crate::domain::{
+    issue_tracker::{ImportedIssue, SyncStatus},
+    story::{AcceptEstimate, IncomingStory, NewStory, RecordActual, Story},
+};
+
+pub struct StoryRepository {
+    db: Arc<SqlitePool>,
+}
+impl StoryRepository {
+    pub fn new(db: &Arc<SqlitePool>) -> Self {
+        Self { db: db.clone() }
+    }
+
+    pub async fn find_by_breakout(&self, breakout_id: i64) -> Result<Vec<Story>, sqlx::Error> {
+        query_as(r#"SELECT * FROM stories WHERE breakout_id = ? ORDER BY position, id"#)
+            .bind(breakout_id)
+            .fetch_all(self.db.as_ref())
+            .await
+    }
+
+    pub async fn create(&self, story: &NewStory) -> Result<Story, sqlx::Error> {
+        query_as(
+            r#"INSERT INTO stories (breakout_id, title, position)
+               VALUES (?, ?, (SELECT COALESCE(MAX(position), 0) + 1 FROM stories WHERE breakout_id = ?))
+               RETURNING *"#,
+        )
+        .bind(story.breakout_id)
+        .bind(&story.title)
+        .bind(story.breakout_id)
+        .fetch_one(self.db.as_ref())
+        .await
+    }
+
+    /// Adds the story for the issue to the end of the backlog, unless the
+    /// issue is already on it. Returns whether it was added.
+    pub async fn import(
+        &self,
+        story: &NewStory,
+        issue: &ImportedIssue,
+    ) -> Result<bool, sqlx::Error> {
+        let result = query(
+            r#"INSERT INTO stories (breakout_id, title, position, external_key, external_url)
+               VALUES (?, ?, (SELECT COALESCE(MAX(position), 0) + 1 FROM stories WHERE breakout_id = ?), ?, ?)
+               ON CONFLICT (breakout_id, external_key) DO NOTHING"#,
+        )
+        .bind(story.breakout_id)
+        .bind(&story.title)
+        .bind(story.breakout_id)
+        .bind(&issue.key)
+        .bind(&issue.url)
+        .execute(self.db.as_ref())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Adds the story to the end of the backlog, unless one with the same
+    /// external key is already on it. Returns the story when it was added.
+    pub async fn push(&self, story: &IncomingStory) -> Result<Option<Story>, sqlx::Error> {
+        query_as(
+            r#"INSERT INTO stories
+                 (breakout_id, title, position, external_key, external_url, description)
+               VALUES (?, ?, (SELECT COALESCE(MAX(position), 0) + 1 FROM stories WHERE breakout_id = ?), ?, ?, ?)
+               ON CONFLICT (breakout_id, external_key) DO NOTHING
+               RETURNING *"#,
+        )
+        .bind(story.breakout_id)
+        .bind(&story.title)
+        .bind(story.breakout_id)
+        .bind(&story.external_key)
+        .bind(&story.link)
+        .bind(&story.description)
+        .fetch_optional(self.db.as_ref())
+        .await
+    }
+
+    pub async fn find_by_external_key(
+        &self,
+        breakout_id: i64,
+        external_key: &str,
+    ) -> Result<Story, sqlx::Error> {
+        query_as(r#"SELECT * FROM stories WHERE breakout_id = ? AND external_key = ?"#)
+            .bind(breakout_id)
+            .bind(external_key)
+            .fetch_one(self.db.as_ref())
+            .await
+    }
+
+    pub async fn set_sync_status(
+        &self,
+        story_id: i64,
+        status: SyncStatus,
+        error: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        query(
+            r#"UPDATE stories SET sync_status = ?, sync_error = ?, updated_at = CURRENT_TIMESTAMP
+               WHERE id = ?"#,
+        )
+        .bind(status)
+        .bind(error)
+        .bind(story_id)
+        .execute(self.db.as_ref())
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find(&self, breakout_id: i64, story_id: i64) -> Result<Story, sqlx::Error> {
+        query_as(r#"SELECT * FROM stories WHERE id = ? AND breakout_id = ?"#)
+            .bind(story_id)
+            .bind(breakout_id)
+            .fetch_one(self.db.as_ref())
+            .await
+    }
+
+    pub async fn accept_estimate(
+        &self,
+        breakout_id: i64,
+        estimate: &AcceptEstimate,
+    ) -> Result<Story, sqlx::Error> {
+        query_as(
+            r#"UPDATE stories
+               SET estimate = ?, estimate_reason = NULLIF(?, ''),
+                   estimated_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+               WHERE id = ? AND breakout_id = ? RETURNING *"#,
+        )
+        .bind(&estimate.estimate)
+        .bind(&estimate.reason)
+        .bind(estimate.story_id)
+        .bind(breakout_id)
+        .fetch_one(self.db.as_ref())
+        .await
+    }
+
+    pub async fn record_actual(
+        &self,
+        breakout_id: i64,
+        actual: &RecordActual,
+    ) -> Result<Story, sqlx::Error> {
+        query_as(
+            r#"UPDATE stories
+               SET actual = ?,
+                   actual_recorded_at = CASE WHEN ? IS NULL THEN NULL ELSE CURRENT_TIMESTAMP END,
+                   updated_at = CURRENT_TIMESTAMP
+               WHERE id = ? AND breakout_id = ? RETURNING *"#,
+        )
+        .bind(actual.actual)
+        .bind(actual.actual)
+        .bind(actual.story_id)
+        .bind(breakout_id)
+        .fetch_one(self.db.as_ref())
+        .await
+    }
+
+    /// Stories across the team's breakouts that have both an estimate and
+    /// an actual.
+    pub async fn find_measured_by_team(&self, team_id: i64) -> Result<Vec<Story>, sqlx::Error> {
+        query_as(
+            r#"SELECT s.* FROM stories s
+               JOIN breakouts b ON b.id = s.breakout_id
+               WHERE b.team_id = ? AND s.estimate IS NOT NULL AND s.actual IS NOT NULL
+               ORDER BY s.actual_recorded_at, s.id"#,
+        )
+        .bind(team_id)
+        .fetch_all(self.db.as_ref())
+        .await
+    }
+}
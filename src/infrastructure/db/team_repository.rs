@@ -0,0 +1,64 @@
+use sqlx::{SqlitePool, query_as};
+use std::sync::Arc;
+
+use crate::domain::{
+    breakout::Breakout,
+    team::{NewTeam, Team},
+};
+
+pub struct TeamRepository {
+    db: Arc<SqlitePool>,
+}
+impl TeamRepository {
+    pub fn new(db: &Arc<SqlitePool>) -> Self {
+        Self { db: db.clone() }
+    }
+
+    pub async fn find(&self, id: i64) -> Result<Team, sqlx::Error> {
+        query_as(r#"SELECT * FROM teams WHERE id = ?"#)
+            .bind(id)
+            .fetch_one(self.db.as_ref())
+            .await
+    }
+
+    pub async fn find_by_lookup_id(&self, lookup_id: &str) -> Result<Team, sqlx::Error> {
+        query_as(r#"SELECT * FROM teams WHERE lookup_id = ?"#)
+            .bind(lookup_id)
+            .fetch_one(self.db.as_ref())
+            .await
+    }
+
+    /// Teams the user started, or that hold a breakout they facilitate.
+    pub async fn find_for_user(&self, user_id: i64) -> Result<Vec<Team>, sqlx::Error> {
+        query_as(
+            r#"SELECT DISTINCT t.* FROM teams t
+               LEFT JOIN breakouts b ON b.team_id = t.id
+               WHERE t.created_by = ? OR b.facilitator_id = ?
+               ORDER BY t.name COLLATE NOCASE"#,
+        )
+        .bind(user_id)
+        .bind(user_id)
+        .fetch_all(self.db.as_ref())
+        .await
+    }
+
+    pub async fn create(&self, team: &NewTeam) -> Result<Team, sqlx::Error> {
+        query_as(
+            r#"INSERT INTO teams (lookup_id, name, created_by)
+               VALUES (?, ?, ?)
+               RETURNING *"#,
+        )
+        .bind(&team.lookup_id)
+        .bind(&team.name)
+        .bind(team.created_by)
+        .fetch_one(self.db.as_ref())
+        .await
+    }
+
+    pub async fn breakouts(&self, team_id: i64) -> Result<Vec<Breakout>, sqlx::Error> {
+        query_as(r#"SELECT * FROM breakouts WHERE team_id = ? ORDER BY title COLLATE NOCASE"#)
+            .bind(team_id)
+            .fetch_all(self.db.as_ref())
+            .await
+    }
+}
@@ -0,0 +1,55 @@
+use sqlx::{SqlitePool, query_as};
+use std::sync::Arc;
+
+use crate::domain::issue_tracker::{SaveTracker, TrackerConnection};
+
+pub struct TrackerRepository {
+    db: Arc<SqlitePool>,
+}
+impl TrackerRepository {
+    pub fn new(db: &Arc<SqlitePool>) -> Self {
+        Self { db: db.clone() }
+    }
+
+    pub async fn find_by_breakout(
+        &self,
+        breakout_id: i64,
+    ) -> Result<Option<TrackerConnection>, sqlx::Error> {
+        query_as(r#"SELECT * FROM issue_trackers WHERE breakout_id = ?"#)
+            .bind(breakout_id)
+            .fetch_optional(self.db.as_ref())
+            .await
+    }
+
+    /// Creates or replaces the breakout's tracker, with `credentials`
+    /// already encrypted.
+    pub async fn save(
+        &self,
+        tracker: &SaveTracker,
+        credentials: &str,
+    ) -> Result<TrackerConnection, sqlx::Error> {
+        query_as(
+            r#"INSERT INTO issue_trackers
+                 (breakout_id, kind, base_url, username, credentials, query, estimate_field,
+                  actual_field)
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+               ON CONFLICT (breakout_id) DO UPDATE SET
+                 kind = excluded.kind, base_url = excluded.base_url,
+                 username = excluded.username, credentials = excluded.credentials,
+                 query = excluded.query, estimate_field = excluded.estimate_field,
+                 actual_field = excluded.actual_field,
+                 updated_at = CURRENT_TIMESTAMP
+               RETURNING *"#,
+        )
+        .bind(tracker.breakout_id)
+        .bind(tracker.kind)
+        .bind(&tracker.base_url)
+        .bind(&tracker.username)
+        .bind(credentials)
+        .bind(&tracker.query)
+        .bind(&tracker.estimate_field)
+        .bind(&tracker.actual_field)
+        .fetch_one(self.db.as_ref())
+        .await
+    }
+}
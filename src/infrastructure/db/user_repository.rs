@@ -1,4 +1,4 @@
-use sqlx::{SqlitePool, query_as};
+use sqlx::{SqlitePool, query, query_as};
 use std::sync::Arc;
 
 use crate::domain::user::{NewUser, UpdateUser, UserRow};
@@ -18,18 +18,51 @@ impl UserRepository {
             .await
     }
 
+    pub async fn find_by_public_id(&self, public_id: &str) -> Result<UserRow, sqlx::Error> {
+        query_as(r#"SELECT * FROM users WHERE public_id = ?"#)
+            .bind(public_id)
+            .fetch_one(self.db.as_ref())
+            .await
+    }
+
     pub async fn create(&self, user: &NewUser) -> Result<UserRow, sqlx::Error> {
-        query_as(r#"INSERT INTO users (lookup_id) VALUES (?) RETURNING *"#)
+        query_as(r#"INSERT INTO users (lookup_id, public_id) VALUES (?, ?) RETURNING *"#)
             .bind(&user.lookup_id)
+            .bind(&user.public_id)
             .fetch_one(self.db.as_ref())
             .await
     }
 
     pub async fn update(&self, user: &UpdateUser) -> Result<UserRow, sqlx::Error> {
-        query_as(r#"UPDATE users SET display_name = ? WHERE id = ? RETURNING *"#)
-            .bind(&user.display_name)
-            .bind(user.id)
-            .fetch_one(self.db.as_ref())
-            .await
+        query_as(
+            r#"UPDATE users SET display_name = ?, updated_at = CURRENT_TIMESTAMP
+               WHERE id = ? RETURNING *"#,
+        )
+        .bind(&user.display_name)
+        .bind(user.id)
+        .fetch_one(self.db.as_ref())
+        .await
+    }
+
+    /// Deletes guests that never chose a display name, haven't been updated
+    /// in the last week and never got anywhere: they aren't a member of any
+    /// breakout, don't facilitate one, didn't start a team and weren't part
+    /// of a saved session. Returns how many rows were removed.
+    pub async fn delete_orphaned_guests(&self) -> Result<u64, sqlx::Error> {
+        let result = query(
+            r#"DELETE FROM users
+               WHERE display_name = 'Guest'
+               AND updated_at < datetime('now', '-7 days')
+               AND NOT EXISTS (SELECT 1 FROM breakout_members m WHERE m.user_id = users.id)
+               AND NOT EXISTS (SELECT 1 FROM breakouts b WHERE b.facilitator_id = users.id)
+               AND NOT EXISTS (SELECT 1 FROM teams t WHERE t.created_by = users.id)
+               AND NOT EXISTS (
+                 SELECT 1 FROM session_participants p WHERE p.user_id = users.id
+               )"#,
+        )
+        .execute(self.db.as_ref())
+        .await?;
+
+        Ok(result.rows_affected())
     }
 }
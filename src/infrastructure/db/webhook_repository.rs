@@ -0,0 +1,138 @@
+use sqlx::{SqlitePool, query, query_as};
+use std::sync::Arc;
+
+use crate::domain::webhook::{
+    DeliveryLogEntry, DeliveryStatus, NewWebhook, Webhook, WebhookDelivery,
+};
+
+pub struct WebhookRepository {
+    db: Arc<SqlitePool>,
+}
+impl WebhookRepository {
+    pub fn new(db: &Arc<SqlitePool>) -> Self {
+        Self { db: db.clone() }
+    }
+
+    pub async fn find_by_breakout(&self, breakout_id: i64) -> Result<Vec<Webhook>, sqlx::Error> {
+        query_as(r#"SELECT * FROM webhooks WHERE breakout_id = ? ORDER BY id"#)
+            .bind(breakout_id)
+            .fetch_all(self.db.as_ref())
+            .await
+    }
+
+    pub async fn find(&self, id: i64) -> Result<Webhook, sqlx::Error> {
+        query_as(r#"SELECT * FROM webhooks WHERE id = ?"#)
+            .bind(id)
+            .fetch_one(self.db.as_ref())
+            .await
+    }
+
+    /// Creates the webhook, with `secret` already encrypted.
+    pub async fn create(&self, webhook: &NewWebhook, secret: &str) -> Result<Webhook, sqlx::Error> {
+        query_as(
+            r#"INSERT INTO webhooks
+                 (breakout_id, url, secret, on_round_revealed, on_estimate_accepted, on_session_ended)
+               VALUES (?, ?, ?, ?, ?, ?)
+               RETURNING *"#,
+        )
+        .bind(webhook.breakout_id)
+        .bind(&webhook.url)
+        .bind(secret)
+        .bind(webhook.on_round_revealed)
+        .bind(webhook.on_estimate_accepted)
+        .bind(webhook.on_session_ended)
+        .fetch_one(self.db.as_ref())
+        .await
+    }
+
+    pub async fn delete(&self, breakout_id: i64, id: i64) -> Result<bool, sqlx::Error> {
+        let result = query(r#"DELETE FROM webhooks WHERE id = ? AND breakout_id = ?"#)
+            .bind(id)
+            .bind(breakout_id)
+            .execute(self.db.as_ref())
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn enqueue(
+        &self,
+        webhook_id: i64,
+        delivery_id: &str,
+        event: &str,
+        payload: &str,
+    ) -> Result<(), sqlx::Error> {
+        query(
+            r#"INSERT INTO webhook_deliveries (webhook_id, delivery_id, event, payload)
+               VALUES (?, ?, ?, ?)"#,
+        )
+        .bind(webhook_id)
+        .bind(delivery_id)
+        .bind(event)
+        .bind(payload)
+        .execute(self.db.as_ref())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Pending deliveries whose next attempt is due, oldest first.
+    pub async fn find_due(&self, limit: i64) -> Result<Vec<WebhookDelivery>, sqlx::Error> {
+        query_as(
+            r#"SELECT * FROM webhook_deliveries
+               WHERE status = 'pending' AND next_attempt_at <= CURRENT_TIMESTAMP
+               ORDER BY next_attempt_at, id
+               LIMIT ?"#,
+        )
+        .bind(limit)
+        .fetch_all(self.db.as_ref())
+        .await
+    }
+
+    /// Records how an attempt went. `retry_in` schedules the next attempt
+    /// that many seconds from now, and only matters while still pending.
+    pub async fn record_attempt(
+        &self,
+        id: i64,
+        status: DeliveryStatus,
+        response_status: Option<u16>,
+        error: Option<&str>,
+        retry_in: u64,
+    ) -> Result<(), sqlx::Error> {
+        query(
+            r#"UPDATE webhook_deliveries
+               SET status = ?, attempts = attempts + 1, response_status = ?, error = ?,
+                   next_attempt_at = datetime('now', '+' || ? || ' seconds'),
+                   updated_at = CURRENT_TIMESTAMP
+               WHERE id = ?"#,
+        )
+        .bind(status)
+        .bind(response_status)
+        .bind(error)
+        .bind(retry_in as i64)
+        .bind(id)
+        .execute(self.db.as_ref())
+        .await?;
+
+        Ok(())
+    }
+
+    /// The breakout's latest deliveries across all of its webhooks.
+    pub async fn recent_deliveries(
+        &self,
+        breakout_id: i64,
+        limit: i64,
+    ) -> Result<Vec<DeliveryLogEntry>, sqlx::Error> {
+        query_as(
+            r#"SELECT d.*, w.url FROM webhook_deliveries d
+               JOIN webhooks w ON w.id = d.webhook_id
+               WHERE w.breakout_id = ?
+               ORDER BY d.id DESC
+               LIMIT ?"#,
+        )
+        .bind(breakout_id)
+        .bind(limit)
+        .fetch_all(self.db.as_ref())
+        .await
+    }
+}
@@ -1 +1,3 @@
+pub mod chat;
 pub mod db;
+pub mod trackers;
@@ -0,0 +1,395 @@
+use async_trait::async_trait;
+use reqwest::{Client, RequestBuilder, Url};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::domain::issue_tracker::{ImportedIssue, IssueTracker, TrackerError};
+
+/// Most issues we import in one go.
+const MAX_ISSUES: usize = 500;
+const PAGE_SIZE: usize = 100;
+
+/// Talks to the GitHub REST and GraphQL APIs with a personal access token.
+pub struct GitHubClient {
+    pub http: Client,
+    /// e.g. "https://api.github.com", or ".../api/v3" for GitHub Enterprise.
+    pub base_url: String,
+    pub token: String,
+    /// A search query, e.g. `repo:acme/api label:ready milestone:"Sprint 4"`.
+    pub query: String,
+    /// Either a label with a `{}` placeholder, e.g. "estimate: {}", or a
+    /// Projects field as "project:<number>:<field name>".
+    pub estimate_field: String,
+    /// Where actual effort is kept, in the same forms as `estimate_field`.
+    pub actual_field: String,
+}
+
+#[derive(Deserialize)]
+struct SearchResponse {
+    items: Vec<GitHubIssue>,
+}
+
+#[derive(Deserialize)]
+struct GitHubIssue {
+    number: u64,
+    title: String,
+    html_url: String,
+    repository_url: String,
+}
+
+#[derive(Deserialize)]
+struct GitHubLabel {
+    name: String,
+}
+
+/// An issue key as we store it, e.g. "acme/api#42".
+struct IssueRef<'a> {
+    owner: &'a str,
+    repo: &'a str,
+    number: u64,
+}
+impl<'a> IssueRef<'a> {
+    fn parse(key: &'a str) -> Result<Self, TrackerError> {
+        let invalid = || TrackerError(format!("{key} isn't a GitHub issue"));
+        let (repository, number) = key.split_once('#').ok_or_else(invalid)?;
+        let (owner, repo) = repository.split_once('/').ok_or_else(invalid)?;
+
+        Ok(Self {
+            owner,
+            repo,
+            number: number.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// A Projects number field given as "project:<number>:<field name>", or
+/// `None` when the setting is a label instead.
+fn project_field(setting: &str) -> Result<Option<(u64, &str)>, TrackerError> {
+    let Some(project) = setting.strip_prefix("project:") else {
+        return Ok(None);
+    };
+    let (number, field) = project.split_once(':').ok_or_else(|| {
+        TrackerError("Projects fields look like project:<number>:<field>".to_string())
+    })?;
+    let number = number
+        .parse()
+        .map_err(|_| TrackerError(format!("{number} isn't a project number")))?;
+
+    Ok(Some((number, field)))
+}
+
+impl GitHubClient {
+    fn request(&self, builder: RequestBuilder) -> RequestBuilder {
+        builder
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .header("User-Agent", "guess-rs")
+    }
+
+    fn graphql_url(&self) -> String {
+        match self.base_url.strip_suffix("/api/v3") {
+            Some(host) => format!("{host}/api/graphql"),
+            None => format!("{}/graphql", self.base_url),
+        }
+    }
+
+    async fn graphql(&self, query: &str, variables: Value) -> Result<Value, TrackerError> {
+        let response: Value = self
+            .request(self.http.post(self.graphql_url()))
+            .json(&json!({ "query": query, "variables": variables }))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| TrackerError(format!("GitHub request failed: {e}")))?
+            .json()
+            .await
+            .map_err(|e| TrackerError(format!("GitHub sent an unexpected response: {e}")))?;
+
+        match response.get("errors") {
+            Some(errors) => Err(TrackerError(format!(
+                "GitHub rejected the request: {errors}"
+            ))),
+            None => Ok(response),
+        }
+    }
+
+    async fn labels(&self, issue: &IssueRef<'_>) -> Result<Vec<GitHubLabel>, TrackerError> {
+        self.request(self.http.get(self.labels_url(issue)))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| TrackerError(format!("GitHub request failed: {e}")))?
+            .json()
+            .await
+            .map_err(|e| TrackerError(format!("GitHub sent an unexpected response: {e}")))
+    }
+
+    fn labels_url(&self, issue: &IssueRef<'_>) -> String {
+        format!(
+            "{}/repos/{}/{}/issues/{}/labels",
+            self.base_url, issue.owner, issue.repo, issue.number
+        )
+    }
+
+    /// Swaps any earlier estimate label for one with the new estimate.
+    async fn write_label(
+        &self,
+        issue: &IssueRef<'_>,
+        template: &str,
+        estimate: &str,
+    ) -> Result<(), TrackerError> {
+        let labels_url = self.labels_url(issue);
+        let (prefix, suffix) = template.split_once("{}").unwrap_or((template, ""));
+        // Every label would match, and be removed, without one of these.
+        if prefix.is_empty() && suffix.is_empty() {
+            return Err(TrackerError(format!(
+                "The label {template} needs some text besides {{}}"
+            )));
+        }
+        let label = format!("{prefix}{estimate}{suffix}");
+
+        let existing = self.labels(issue).await?;
+
+        for old in existing
+            .iter()
+            .filter(|l| l.name != label && l.name.starts_with(prefix) && l.name.ends_with(suffix))
+        {
+            let mut url = Url::parse(&labels_url)
+                .map_err(|e| TrackerError(format!("Invalid GitHub URL: {e}")))?;
+            url.path_segments_mut()
+                .map_err(|_| TrackerError("Invalid GitHub URL".to_string()))?
+                .push(&old.name);
+
+            self.request(self.http.delete(url))
+                .send()
+                .await
+                .and_then(|r| r.error_for_status())
+                .map_err(|e| TrackerError(format!("Failed to remove label {}: {e}", old.name)))?;
+        }
+
+        self.request(self.http.post(&labels_url))
+            .json(&json!({ "labels": [label] }))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| TrackerError(format!("Failed to add label {label}: {e}")))?;
+
+        Ok(())
+    }
+
+    /// The number in the first label that matches the template.
+    async fn read_label(
+        &self,
+        issue: &IssueRef<'_>,
+        template: &str,
+    ) -> Result<Option<f64>, TrackerError> {
+        let (prefix, suffix) = template.split_once("{}").unwrap_or((template, ""));
+
+        Ok(self.labels(issue).await?.iter().find_map(|label| {
+            label
+                .name
+                .strip_prefix(prefix)
+                .and_then(|rest| rest.strip_suffix(suffix))
+                .and_then(|value| value.trim().parse().ok())
+        }))
+    }
+
+    /// Reads a number field from the issue's item in one of the owner's
+    /// Projects.
+    async fn read_project_field(
+        &self,
+        issue: &IssueRef<'_>,
+        project_number: u64,
+        field_name: &str,
+    ) -> Result<Option<f64>, TrackerError> {
+        let response = self
+            .graphql(
+                r#"query($owner: String!, $repo: String!, $number: Int!, $field: String!) {
+                     repository(owner: $owner, name: $repo) {
+                       issue(number: $number) {
+                         projectItems(first: 50) {
+                           nodes {
+                             project { number }
+                             fieldValueByName(name: $field) {
+                               ... on ProjectV2ItemFieldNumberValue { number }
+                             }
+                           }
+                         }
+                       }
+                     }
+                   }"#,
+                json!({
+                    "owner": issue.owner,
+                    "repo": issue.repo,
+                    "number": issue.number,
+                    "field": field_name,
+                }),
+            )
+            .await?;
+
+        let item = response["data"]["repository"]["issue"]["projectItems"]["nodes"]
+            .as_array()
+            .and_then(|items| {
+                items
+                    .iter()
+                    .find(|item| item["project"]["number"].as_u64() == Some(project_number))
+            })
+            .ok_or_else(|| TrackerError(format!("The issue isn't in project {project_number}")))?;
+
+        Ok(item["fieldValueByName"]["number"].as_f64())
+    }
+
+    /// Sets a number field on the issue's item in one of the owner's
+    /// Projects.
+    async fn write_project_field(
+        &self,
+        issue: &IssueRef<'_>,
+        project_number: u64,
+        field_name: &str,
+        estimate: &str,
+    ) -> Result<(), TrackerError> {
+        let value: f64 = estimate
+            .parse()
+            .map_err(|_| TrackerError(format!("{estimate} can't go in a number field")))?;
+
+        let response = self
+            .graphql(
+                r#"query($owner: String!, $repo: String!, $number: Int!, $field: String!) {
+                     repository(owner: $owner, name: $repo) {
+                       issue(number: $number) {
+                         projectItems(first: 50) {
+                           nodes {
+                             id
+                             project {
+                               id
+                               number
+                               field(name: $field) { ... on ProjectV2Field { id } }
+                             }
+                           }
+                         }
+                       }
+                     }
+                   }"#,
+                json!({
+                    "owner": issue.owner,
+                    "repo": issue.repo,
+                    "number": issue.number,
+                    "field": field_name,
+                }),
+            )
+            .await?;
+
+        let item = response["data"]["repository"]["issue"]["projectItems"]["nodes"]
+            .as_array()
+            .and_then(|items| {
+                items
+                    .iter()
+                    .find(|item| item["project"]["number"].as_u64() == Some(project_number))
+            })
+            .ok_or_else(|| TrackerError(format!("The issue isn't in project {project_number}")))?;
+
+        let (Some(item_id), Some(project_id), Some(field_id)) = (
+            item["id"].as_str(),
+            item["project"]["id"].as_str(),
+            item["project"]["field"]["id"].as_str(),
+        ) else {
+            return Err(TrackerError(format!(
+                "Project {project_number} has no number field called {field_name}"
+            )));
+        };
+
+        self.graphql(
+            r#"mutation($project: ID!, $item: ID!, $field: ID!, $value: Float!) {
+                 updateProjectV2ItemFieldValue(input: {
+                   projectId: $project, itemId: $item, fieldId: $field, value: { number: $value }
+                 }) { projectV2Item { id } }
+               }"#,
+            json!({
+                "project": project_id,
+                "item": item_id,
+                "field": field_id,
+                "value": value,
+            }),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl IssueTracker for GitHubClient {
+    async fn fetch_issues(&self) -> Result<Vec<ImportedIssue>, TrackerError> {
+        let mut query = self.query.clone();
+        if !query.contains("is:issue") {
+            query.push_str(" is:issue");
+        }
+        if !query.contains("is:open") && !query.contains("is:closed") {
+            query.push_str(" is:open");
+        }
+
+        let mut issues = vec![];
+        for page in 1.. {
+            let response: SearchResponse = self
+                .request(self.http.get(format!("{}/search/issues", self.base_url)))
+                .query(&[
+                    ("q", query.clone()),
+                    ("per_page", PAGE_SIZE.to_string()),
+                    ("page", page.to_string()),
+                ])
+                .send()
+                .await
+                .and_then(|r| r.error_for_status())
+                .map_err(|e| TrackerError(format!("GitHub search failed: {e}")))?
+                .json()
+                .await
+                .map_err(|e| TrackerError(format!("GitHub sent an unexpected response: {e}")))?;
+
+            let count = response.items.len();
+            issues.extend(response.items.into_iter().map(|issue| {
+                let repository = issue
+                    .repository_url
+                    .split_once("/repos/")
+                    .map_or("", |(_, repository)| repository);
+
+                ImportedIssue {
+                    key: format!("{repository}#{}", issue.number),
+                    title: issue.title,
+                    url: issue.html_url,
+                }
+            }));
+
+            if count < PAGE_SIZE || issues.len() >= MAX_ISSUES {
+                break;
+            }
+        }
+
+        issues.truncate(MAX_ISSUES);
+        Ok(issues)
+    }
+
+    async fn write_estimate(&self, key: &str, estimate: &str) -> Result<(), TrackerError> {
+        let issue = IssueRef::parse(key)?;
+
+        match project_field(&self.estimate_field)? {
+            Some((number, field)) => {
+                self.write_project_field(&issue, number, field, estimate)
+                    .await
+            }
+            None => {
+                self.write_label(&issue, &self.estimate_field, estimate)
+                    .await
+            }
+        }
+    }
+
+    async fn fetch_actual(&self, key: &str) -> Result<Option<f64>, TrackerError> {
+        let issue = IssueRef::parse(key)?;
+
+        match project_field(&self.actual_field)? {
+            Some((number, field)) => self.read_project_field(&issue, number, field).await,
+            None => self.read_label(&issue, &self.actual_field).await,
+        }
+    }
+}
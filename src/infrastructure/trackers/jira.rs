@@ -0,0 +1,139 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::domain::issue_tracker::{ImportedIssue, IssueTracker, TrackerError};
+
+/// Most issues we import in one go.
+const MAX_ISSUES: usize = 500;
+const PAGE_SIZE: usize = 100;
+/// Jira's time tracking fields, which hold seconds rather than points.
+const TIME_FIELDS: [&str; 2] = ["timespent", "aggregatetimespent"];
+
+/// Talks to the Jira Cloud REST API with an account email and API token.
+pub struct JiraClient {
+    pub http: Client,
+    pub base_url: String,
+    pub email: String,
+    pub api_token: String,
+    pub jql: String,
+    /// The story points field, e.g. "customfield_10016".
+    pub estimate_field: String,
+    /// Where actual effort is kept, e.g. a custom number field or
+    /// "timespent" for logged work.
+    pub actual_field: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchResponse {
+    issues: Vec<JiraIssue>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct JiraIssue {
+    key: String,
+    fields: JiraFields,
+}
+
+#[derive(Deserialize)]
+struct JiraFields {
+    summary: String,
+}
+
+#[async_trait]
+impl IssueTracker for JiraClient {
+    async fn fetch_issues(&self) -> Result<Vec<ImportedIssue>, TrackerError> {
+        let mut issues = vec![];
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let mut query = vec![
+                ("jql", self.jql.clone()),
+                ("fields", "summary".to_string()),
+                ("maxResults", PAGE_SIZE.to_string()),
+            ];
+            if let Some(token) = page_token {
+                query.push(("nextPageToken", token));
+            }
+
+            let page: SearchResponse = self
+                .http
+                .get(format!("{}/rest/api/3/search/jql", self.base_url))
+                .basic_auth(&self.email, Some(&self.api_token))
+                .query(&query)
+                .send()
+                .await
+                .and_then(|r| r.error_for_status())
+                .map_err(|e| TrackerError(format!("Jira search failed: {e}")))?
+                .json()
+                .await
+                .map_err(|e| TrackerError(format!("Jira sent an unexpected response: {e}")))?;
+
+            issues.extend(page.issues.into_iter().map(|issue| ImportedIssue {
+                url: format!("{}/browse/{}", self.base_url, issue.key),
+                key: issue.key,
+                title: issue.fields.summary,
+            }));
+
+            match page.next_page_token {
+                Some(token) if issues.len() < MAX_ISSUES => page_token = Some(token),
+                _ => break,
+            }
+        }
+
+        issues.truncate(MAX_ISSUES);
+        Ok(issues)
+    }
+
+    async fn write_estimate(&self, key: &str, estimate: &str) -> Result<(), TrackerError> {
+        // Story points are numeric in Jira, so only fall back to text for
+        // estimates like "?".
+        let value = match estimate.parse::<f64>() {
+            Ok(points) => json!(points),
+            Err(_) => Value::String(estimate.to_string()),
+        };
+
+        self.http
+            .put(format!("{}/rest/api/3/issue/{key}", self.base_url))
+            .basic_auth(&self.email, Some(&self.api_token))
+            .json(&json!({ "fields": { &self.estimate_field: value } }))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| TrackerError(format!("Jira update of {key} failed: {e}")))?;
+
+        Ok(())
+    }
+
+    async fn fetch_actual(&self, key: &str) -> Result<Option<f64>, TrackerError> {
+        let issue: Value = self
+            .http
+            .get(format!("{}/rest/api/3/issue/{key}", self.base_url))
+            .basic_auth(&self.email, Some(&self.api_token))
+            .query(&[("fields", &self.actual_field)])
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| TrackerError(format!("Jira lookup of {key} failed: {e}")))?
+            .json()
+            .await
+            .map_err(|e| TrackerError(format!("Jira sent an unexpected response: {e}")))?;
+
+        let value = match &issue["fields"][&self.actual_field] {
+            Value::Null => return Ok(None),
+            Value::Number(number) => number.as_f64(),
+            Value::String(text) => text.trim().parse().ok(),
+            _ => None,
+        }
+        .ok_or_else(|| TrackerError(format!("{} of {key} isn't a number", self.actual_field)))?;
+
+        // Logged work comes in seconds, which we turn into hours.
+        match TIME_FIELDS.contains(&self.actual_field.as_str()) {
+            true => Ok(Some(value / 3600.0)),
+            false => Ok(Some(value)),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use reqwest::Client;
+
+use crate::domain::issue_tracker::{IssueTracker, TrackerConnection, TrackerKind};
+
+pub mod github;
+pub mod jira;
+
+/// Builds a client for the connection's tracker, given its decrypted
+/// credentials.
+pub fn client(
+    http: &Client,
+    connection: &TrackerConnection,
+    secret: String,
+) -> Box<dyn IssueTracker> {
+    match connection.kind {
+        TrackerKind::Jira => Box::new(jira::JiraClient {
+            http: http.clone(),
+            base_url: connection.base_url.clone(),
+            email: connection.username.clone(),
+            api_token: secret,
+            jql: connection.query.clone(),
+            estimate_field: connection.estimate_field.clone(),
+            actual_field: connection.actual_field.clone(),
+        }),
+        TrackerKind::GitHub => Box::new(github::GitHubClient {
+            http: http.clone(),
+            base_url: connection.base_url.clone(),
+            token: secret,
+            query: connection.query.clone(),
+            estimate_field: connection.estimate_field.clone(),
+            actual_field: connection.actual_field.clone(),
+        }),
+    }
+}
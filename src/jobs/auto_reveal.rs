@@ -0,0 +1,23 @@
+use std::time::Duration;
+
+use crate::{BreakoutChannels, domain::breakout_channel::Countdown};
+
+/// Counts down to an automatic reveal, letting everyone know every second,
+/// and stops quietly if the countdown gets cancelled along the way.
+pub async fn run(channels: BreakoutChannels, lookup_id: String, countdown: Countdown) {
+    let mut interval = tokio::time::interval(Duration::from_secs(1));
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+
+        let mut channels = channels.lock().await;
+        let ticking = channels
+            .get_mut(&lookup_id)
+            .is_some_and(|channel| channel.tick_countdown(countdown.generation));
+
+        if !ticking {
+            break;
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use log::{error, warn};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::{SharedState, domain::room_event::RoomEventKind};
+
+/// Tells the breakout's chats when one of its sessions starts, and how it
+/// went once it ends.
+pub async fn run(state: SharedState) {
+    let mut events = state.room_events.subscribe();
+
+    loop {
+        let event = match events.recv().await {
+            Ok(event) => event,
+            Err(RecvError::Lagged(skipped)) => {
+                warn!("Chat notifications fell behind, skipped {skipped} room events.");
+                continue;
+            }
+            Err(RecvError::Closed) => break,
+        };
+        if !matches!(
+            event.kind,
+            RoomEventKind::SessionStarted(_) | RoomEventKind::SessionEnded(_)
+        ) {
+            continue;
+        }
+
+        let breakout = match state
+            .breakout_service
+            .find_by_lookup_id(event.lookup_id.clone())
+            .await
+        {
+            Ok(breakout) => breakout,
+            Err(e) => {
+                error!("Failed to find breakout {}: {e}", event.lookup_id);
+                continue;
+            }
+        };
+
+        let url = state.app_info.breakout_url(&breakout.lookup_id);
+        for notifier in state.notification_service.notifiers(&breakout) {
+            let sent = match &event.kind {
+                RoomEventKind::SessionEnded(session) => {
+                    notifier.session_summary(&breakout, &url, session).await
+                }
+                _ => notifier.session_started(&breakout, &url).await,
+            };
+            if let Err(e) = sent {
+                warn!(
+                    "Failed to post {} of breakout {} to chat: {e}",
+                    event.kind.name(),
+                    breakout.id
+                );
+            }
+        }
+    }
+}
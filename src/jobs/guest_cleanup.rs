@@ -0,0 +1,23 @@
+use log::{error, info};
+use std::time::Duration;
+
+use crate::SharedState;
+
+/// How often we sweep for orphaned guests.
+const INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Periodically removes guest users that never picked a display name and
+/// have not been touched in a while.
+pub async fn run(state: SharedState) {
+    let mut interval = tokio::time::interval(INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        match state.user_service.delete_orphaned_guests().await {
+            Ok(0) => {}
+            Ok(count) => info!("🧹 Removed {count} orphaned guest users."),
+            Err(e) => error!("Failed to remove orphaned guest users: {e}"),
+        }
+    }
+}
@@ -0,0 +1,8 @@
+pub mod auto_reveal;
+pub mod chat_notifications;
+pub mod guest_cleanup;
+pub mod round_timer;
+pub mod session_end;
+pub mod tracker_sync;
+pub mod webhook_delivery;
+pub mod webhook_queue;
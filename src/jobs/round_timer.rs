@@ -0,0 +1,23 @@
+use std::time::Duration;
+
+use crate::{BreakoutChannels, domain::round_timer::RoundTimer};
+
+/// Ticks a round timer every second until it runs out, or until it is
+/// paused, stopped or replaced.
+pub async fn run(channels: BreakoutChannels, lookup_id: String, timer: RoundTimer) {
+    let mut interval = tokio::time::interval(Duration::from_secs(1));
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+
+        let mut channels = channels.lock().await;
+        let ticking = channels
+            .get_mut(&lookup_id)
+            .is_some_and(|channel| channel.tick_timer(timer.generation));
+
+        if !ticking {
+            break;
+        }
+    }
+}
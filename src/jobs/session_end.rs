@@ -0,0 +1,39 @@
+use log::error;
+use std::time::Duration;
+
+use crate::SharedState;
+
+/// How long a room stays empty before its session counts as over, so a
+/// refresh or a flaky connection doesn't end it.
+const GRACE_PERIOD: Duration = Duration::from_secs(60);
+
+/// Ends the breakout's session once the grace period is up, unless somebody
+/// came back in the meantime, and keeps it for the session report.
+pub async fn run(state: SharedState, lookup_id: String, generation: u64) {
+    tokio::time::sleep(GRACE_PERIOD).await;
+
+    let record = {
+        let mut channels = state.breakout_channels.lock().await;
+        channels
+            .get_mut(&lookup_id)
+            .and_then(|channel| channel.end_session(generation))
+    };
+    let Some(record) = record.filter(|record| !record.is_empty()) else {
+        return;
+    };
+
+    let breakout = match state
+        .breakout_service
+        .find_by_lookup_id(lookup_id.clone())
+        .await
+    {
+        Ok(breakout) => breakout,
+        Err(e) => {
+            error!("Failed to find breakout {lookup_id}: {e}");
+            return;
+        }
+    };
+    if let Err(e) = state.session_service.save(breakout.id, &record).await {
+        error!("Failed to save session of breakout {}: {e}", breakout.id);
+    }
+}
@@ -0,0 +1,100 @@
+use log::{error, warn};
+use std::time::Duration;
+
+use crate::{
+    SharedState,
+    domain::{
+        breakout::Breakout,
+        issue_tracker::{IssueTracker, SyncStatus, TrackerError},
+        story::Story,
+    },
+};
+
+/// How many times we try to write an estimate back before giving up.
+pub const ATTEMPTS: u32 = 4;
+/// How long we wait after the first failed attempt.
+const BACKOFF: Duration = Duration::from_secs(2);
+
+/// Writes a story's accepted estimate back to the tracker it came from,
+/// backing off between attempts, and keeps the story's sync status up to
+/// date as it goes.
+pub async fn run(state: SharedState, breakout: Breakout, story: Story) {
+    let (Some(key), Some(estimate)) = (&story.external_key, &story.estimate) else {
+        return;
+    };
+
+    let connection = match state.tracker_service.find_by_breakout(breakout.id).await {
+        Ok(Some(connection)) => connection,
+        Ok(None) => return,
+        Err(e) => {
+            error!("Failed to find tracker of breakout {}: {e}", breakout.id);
+            return;
+        }
+    };
+
+    set_status(&state, &breakout, &story, SyncStatus::Pending, None).await;
+
+    let written = match state.tracker_service.client(&connection) {
+        Ok(tracker) => write_back(tracker.as_ref(), key, estimate, BACKOFF).await,
+        Err(e) => Err(TrackerError(e.message().to_string())),
+    };
+
+    match written {
+        Ok(()) => set_status(&state, &breakout, &story, SyncStatus::Synced, None).await,
+        Err(e) => {
+            warn!("Giving up on syncing story {}: {e}", story.id);
+            let error = e.to_string();
+            set_status(&state, &breakout, &story, SyncStatus::Failed, Some(&error)).await;
+        }
+    }
+}
+
+/// Writes an estimate to the tracker, trying again up to `ATTEMPTS` times
+/// and waiting twice as long as the time before after each failure,
+/// starting at `backoff`. Gives the last error when every attempt failed.
+pub async fn write_back(
+    tracker: &dyn IssueTracker,
+    key: &str,
+    estimate: &str,
+    backoff: Duration,
+) -> Result<(), TrackerError> {
+    let mut attempt = 1;
+    loop {
+        match tracker.write_estimate(key, estimate).await {
+            Ok(()) => return Ok(()),
+            Err(e) if attempt == ATTEMPTS => return Err(e),
+            Err(e) => {
+                warn!("Failed to write estimate of {key} (attempt {attempt}): {e}");
+                tokio::time::sleep(backoff * 2u32.pow(attempt - 1)).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+async fn set_status(
+    state: &SharedState,
+    breakout: &Breakout,
+    story: &Story,
+    status: SyncStatus,
+    error: Option<&str>,
+) {
+    if let Err(e) = state
+        .story_service
+        .set_sync_status(story.id, status, error)
+        .await
+    {
+        error!("Failed to update sync status of story {}: {e}", story.id);
+        return;
+    }
+
+    match state.story_service.find_by_breakout(breakout.id).await {
+        Ok(backlog) => {
+            let mut channels = state.breakout_channels.lock().await;
+            if let Some(channel) = channels.get_mut(&breakout.lookup_id) {
+                channel.set_backlog(backlog);
+            }
+        }
+        Err(e) => error!("Failed to load backlog of breakout {}: {e}", breakout.id),
+    }
+}
@@ -0,0 +1,89 @@
+use futures_util::stream::{self, StreamExt};
+use log::{error, warn};
+use std::time::Duration;
+
+use crate::{
+    SharedState,
+    domain::webhook::{DeliveryStatus, WebhookDelivery},
+};
+
+/// How often we look for deliveries that are due.
+const INTERVAL: Duration = Duration::from_secs(5);
+/// How many deliveries we send per sweep.
+const BATCH: i64 = 50;
+/// How many of them are in flight at once, so a few slow receivers don't
+/// hold up everyone else's.
+const CONCURRENCY: usize = 10;
+/// How many times we try a delivery before giving up on it.
+const ATTEMPTS: i64 = 6;
+/// How long we wait after the first failed attempt, doubling every time.
+const BACKOFF_SECONDS: u64 = 30;
+
+/// Sends queued webhook deliveries, retrying failed ones with exponential
+/// backoff. The queue lives in the database, so nothing is lost on restart.
+pub async fn run(state: SharedState) {
+    let mut interval = tokio::time::interval(INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let due = match state.webhook_service.find_due(BATCH).await {
+            Ok(due) => due,
+            Err(e) => {
+                error!("Failed to find due webhook deliveries: {e}");
+                continue;
+            }
+        };
+
+        stream::iter(due)
+            .for_each_concurrent(CONCURRENCY, |delivery| try_delivery(&state, delivery))
+            .await;
+    }
+}
+
+/// Sends the delivery once, recording how it went.
+async fn try_delivery(state: &SharedState, delivery: WebhookDelivery) {
+    let attempt = delivery.attempts + 1;
+    let recorded = match state.webhook_service.deliver(&delivery).await {
+        Ok(response_status) => {
+            state
+                .webhook_service
+                .record_attempt(
+                    &delivery,
+                    DeliveryStatus::Delivered,
+                    Some(response_status),
+                    None,
+                    0,
+                )
+                .await
+        }
+        Err(e) => {
+            let status = match attempt >= ATTEMPTS {
+                true => DeliveryStatus::Failed,
+                false => DeliveryStatus::Pending,
+            };
+            warn!(
+                "Failed to deliver webhook {} (attempt {attempt}): {e}",
+                delivery.delivery_id
+            );
+
+            state
+                .webhook_service
+                .record_attempt(
+                    &delivery,
+                    status,
+                    e.response_status,
+                    Some(&e.message),
+                    BACKOFF_SECONDS * 2u64.pow(delivery.attempts as u32),
+                )
+                .await
+        }
+    };
+
+    if let Err(e) = recorded {
+        error!(
+            "Failed to record webhook delivery {}: {e}",
+            delivery.delivery_id
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use log::error;
+use tokio::sync::mpsc::UnboundedReceiver;
+
+use crate::{SharedState, domain::room_event::RoomEvent};
+
+/// Takes the room events of every breakout off the webhooks' queue, which
+/// holds on to all of them until we get here, and queues a delivery for
+/// each webhook that wants to hear about them.
+pub async fn run(state: SharedState, mut events: UnboundedReceiver<RoomEvent>) {
+    while let Some(event) = events.recv().await {
+        let breakout = match state
+            .breakout_service
+            .find_by_lookup_id(event.lookup_id.clone())
+            .await
+        {
+            Ok(breakout) => breakout,
+            Err(e) => {
+                error!("Failed to find breakout {}: {e}", event.lookup_id);
+                continue;
+            }
+        };
+
+        if let Err(e) = state
+            .webhook_service
+            .enqueue(&event, &breakout, &state.app_info)
+            .await
+        {
+            error!(
+                "Failed to queue {} webhooks of breakout {}: {e}",
+                event.kind.name(),
+                breakout.id
+            );
+        }
+    }
+}
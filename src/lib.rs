@@ -1,6 +1,7 @@
 use axum::{
     Router,
     http::{HeaderValue, header::CACHE_CONTROL},
+    middleware::from_fn_with_state,
 };
 use sqlx::{Pool, Sqlite};
 use std::collections::HashMap;
@@ -11,15 +12,22 @@ use tower_http::{
 };
 
 use crate::{
-    application::{BreakoutService, UserService},
-    domain::breakout_channel::BreakoutChannel,
+    application::{
+        BreakoutService, MembershipService, NotificationService, SessionService, SlackService,
+        StoryService, TeamService, TrackerService, UserService, WebhookService,
+    },
+    domain::{breakout_channel::BreakoutChannel, room_event::RoomEvents},
     infrastructure::db::Database,
+    middleware::identity::CookieKeys,
 };
 
 pub mod application;
 pub mod domain;
+pub mod error;
 pub mod extract;
 pub mod infrastructure;
+pub mod jobs;
+pub mod middleware;
 pub mod routes;
 pub mod util;
 
@@ -40,18 +48,45 @@ pub async fn start() {
 async fn initialize() -> Router {
     let db = Arc::new(Database::initialize().await);
     let app_info = AppInfo::new();
-    let state = Arc::new(AppState::new(&db, app_info.clone()));
+    let (room_events, queued_events) = RoomEvents::new();
+    let state = Arc::new(AppState::new(&db, app_info.clone(), room_events));
     let serve_static = Router::new()
         .nest_service("/assets", ServeDir::new("public"))
         .layer(SetResponseHeaderLayer::if_not_present(
             CACHE_CONTROL,
             HeaderValue::from_static("public, max-age=31536000"),
         ));
+    let pages = Router::new()
+        .merge(routes::homepage::routes())
+        .merge(routes::breakout::routes())
+        .merge(routes::display::routes())
+        .merge(routes::join::routes())
+        .merge(routes::notifications::routes())
+        .merge(routes::report::routes())
+        .merge(routes::story::routes())
+        .merge(routes::team::routes())
+        .merge(routes::tracker::routes())
+        .merge(routes::webhook::routes())
+        .fallback(routes::not_found)
+        .layer(from_fn_with_state(
+            state.clone(),
+            middleware::errors::render_errors,
+        ))
+        .layer(from_fn_with_state(
+            state.clone(),
+            middleware::identity::identity,
+        ));
+
+    tokio::spawn(jobs::guest_cleanup::run(state.clone()));
+    tokio::spawn(jobs::webhook_queue::run(state.clone(), queued_events));
+    tokio::spawn(jobs::webhook_delivery::run(state.clone()));
+    tokio::spawn(jobs::chat_notifications::run(state.clone()));
 
     Router::new()
         .merge(serve_static)
-        .merge(routes::homepage::routes())
-        .merge(routes::breakout::routes())
+        .merge(pages)
+        .merge(routes::hooks::routes())
+        .merge(routes::slack::routes())
         .with_state(state)
         .layer(CompressionLayer::new())
 }
@@ -70,6 +105,28 @@ impl AppInfo {
             website_url: env::var("APP_WEBSITE_URL").expect("APP_WEBSITE_URL not defined"),
         }
     }
+
+    /// The absolute URL people use to join a breakout.
+    pub fn breakout_url(&self, lookup_id: &str) -> String {
+        format!(
+            "{}/breakout/{lookup_id}",
+            self.website_url.trim_end_matches('/')
+        )
+    }
+    /// The absolute, read-only URL for showing a breakout on a shared screen.
+    pub fn display_url(&self, display_token: &str) -> String {
+        format!(
+            "{}/display/{display_token}",
+            self.website_url.trim_end_matches('/')
+        )
+    }
+    /// The absolute URL other tools post stories to.
+    pub fn intake_url(&self, intake_token: &str) -> String {
+        format!(
+            "{}/hooks/breakout/{intake_token}/stories",
+            self.website_url.trim_end_matches('/')
+        )
+    }
 }
 
 pub type BreakoutChannels = Arc<Mutex<HashMap<String, BreakoutChannel>>>;
@@ -79,16 +136,37 @@ pub struct AppState {
     pub app_info: AppInfo,
     pub breakout_service: BreakoutService,
     pub user_service: UserService,
+    pub membership_service: MembershipService,
+    pub story_service: StoryService,
+    pub tracker_service: TrackerService,
+    pub webhook_service: WebhookService,
+    pub session_service: SessionService,
+    pub team_service: TeamService,
+    pub slack_service: SlackService,
+    pub notification_service: NotificationService,
     pub breakout_channels: BreakoutChannels,
+    /// Everything worth telling the outside world about, from every breakout.
+    pub room_events: RoomEvents,
+    pub cookie_keys: CookieKeys,
 }
 impl AppState {
-    pub fn new(db: &Arc<Pool<Sqlite>>, app_info: AppInfo) -> Self {
+    pub fn new(db: &Arc<Pool<Sqlite>>, app_info: AppInfo, room_events: RoomEvents) -> Self {
         let breakout_channels: BreakoutChannels = Arc::new(Mutex::new(HashMap::new()));
         Self {
             app_info: app_info.clone(),
             breakout_service: BreakoutService::new(db),
             user_service: UserService::new(db),
+            membership_service: MembershipService::new(db),
+            story_service: StoryService::new(db),
+            tracker_service: TrackerService::new(db),
+            webhook_service: WebhookService::new(db),
+            session_service: SessionService::new(db),
+            team_service: TeamService::new(db),
+            slack_service: SlackService::new(),
+            notification_service: NotificationService::new(),
             breakout_channels,
+            room_events,
+            cookie_keys: CookieKeys::new(),
         }
     }
 }
@@ -0,0 +1,50 @@
+use askama::Template;
+use askama_web::WebTemplate;
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::{SharedState, error::AppError, routes::SharedContext};
+
+#[derive(Template, WebTemplate)]
+#[template(path = "error.html")]
+struct ErrorTemplate {
+    shared: SharedContext,
+    error: AppError,
+}
+
+#[derive(Template, WebTemplate)]
+#[template(path = "error_modal.html")]
+struct ErrorModalTemplate {
+    error: AppError,
+}
+
+/// Renders any `AppError` a handler returned. Regular requests get a full
+/// error page; htmx requests get a fragment shown in the modal, since htmx
+/// will not swap in a 4xx/5xx response on its own.
+pub async fn render_errors(
+    State(state): State<SharedState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let is_htmx = request.headers().contains_key("HX-Request");
+    let response = next.run(request).await;
+
+    let Some(error) = response.extensions().get::<AppError>().cloned() else {
+        return response;
+    };
+
+    if is_htmx {
+        let mut headers = HeaderMap::new();
+        headers.insert("HX-Retarget", "#modal".parse().unwrap());
+        headers.insert("HX-Reswap", "innerHTML".parse().unwrap());
+        return (StatusCode::OK, headers, ErrorModalTemplate { error }).into_response();
+    }
+
+    let status = error.status();
+    let shared = SharedContext::new(&state.app_info, None);
+    (status, ErrorTemplate { shared, error }).into_response()
+}
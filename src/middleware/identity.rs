@@ -0,0 +1,126 @@
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use axum_extra::extract::{
+    CookieJar, PrivateCookieJar,
+    cookie::{Cookie, Key, SameSite},
+};
+use log::warn;
+use std::env;
+use time::Duration;
+
+use crate::{SharedState, domain::user::NewUser};
+
+pub const WHOAMI_COOKIE: &str = "whoami";
+
+/// The identity of whoever is making the request. This is only an id; the
+/// matching `users` row is not created until they actually join a breakout.
+#[derive(Clone)]
+pub struct Whoami(pub String);
+
+/// Keys used to encrypt the identity cookie. New cookies are always written
+/// with `current`; `previous` keys are only used to read cookies issued
+/// before a rotation, which are then re-issued with the current key.
+#[derive(Clone)]
+pub struct CookieKeys {
+    pub current: Key,
+    pub previous: Vec<Key>,
+}
+impl CookieKeys {
+    /// Reads `COOKIE_SECRET` and the optional, comma-separated
+    /// `COOKIE_SECRET_PREVIOUS`. Each secret must be at least 32 bytes.
+    pub fn new() -> Self {
+        let current = match env::var("COOKIE_SECRET") {
+            Ok(secret) => derive_key(&secret),
+            Err(_) => {
+                warn!("COOKIE_SECRET not defined, identities will not survive a restart.");
+                Key::generate()
+            }
+        };
+        let previous = env::var("COOKIE_SECRET_PREVIOUS")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|secret| !secret.is_empty())
+            .map(derive_key)
+            .collect();
+
+        Self { current, previous }
+    }
+
+    /// Decrypts the identity cookie, returning its value and whether it was
+    /// encrypted with an older key.
+    fn whoami(&self, request: &Request) -> Option<(String, bool)> {
+        std::iter::once(&self.current)
+            .chain(&self.previous)
+            .enumerate()
+            .find_map(|(index, key)| {
+                PrivateCookieJar::from_headers(request.headers(), key.clone())
+                    .get(WHOAMI_COOKIE)
+                    .map(|cookie| (cookie.value().to_string(), index > 0))
+            })
+    }
+}
+impl Default for CookieKeys {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Makes sure every request carries a `whoami` identity. Visitors without a
+/// valid cookie get a freshly minted guest id, which is set on the response
+/// right away so that it stays stable across the rest of their requests.
+/// Cookies from before identities were encrypted are read once and
+/// re-issued encrypted, so nobody loses their rooms over the switch.
+pub async fn identity(
+    State(state): State<SharedState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let (lookup_id, reissue) = match state.cookie_keys.whoami(&request) {
+        Some((lookup_id, rotated)) => (lookup_id, rotated),
+        None => (
+            legacy_whoami(&request).unwrap_or_else(|| NewUser::default().lookup_id),
+            true,
+        ),
+    };
+    request.extensions_mut().insert(Whoami(lookup_id.clone()));
+
+    let response = next.run(request).await;
+
+    if !reissue {
+        return response;
+    }
+
+    let jar = PrivateCookieJar::new(state.cookie_keys.current.clone());
+    (jar.add(whoami_cookie(lookup_id)), response).into_response()
+}
+
+/// The plain identity cookie we used to set, which held the user's lookup
+/// id as it is. Encrypted cookies never look like one.
+fn legacy_whoami(request: &Request) -> Option<String> {
+    CookieJar::from_headers(request.headers())
+        .get(WHOAMI_COOKIE)
+        .map(|cookie| cookie.value().to_string())
+        .filter(|value| uuid::Uuid::parse_str(value).is_ok())
+}
+
+fn derive_key(secret: &str) -> Key {
+    assert!(
+        secret.len() >= 32,
+        "cookie secrets must be at least 32 bytes"
+    );
+    Key::derive_from(secret.as_bytes())
+}
+
+fn whoami_cookie(lookup_id: String) -> Cookie<'static> {
+    Cookie::build((WHOAMI_COOKIE, lookup_id))
+        .path("/")
+        .http_only(true)
+        .same_site(SameSite::None)
+        .max_age(Duration::days(365))
+        .secure(true)
+        .build()
+}
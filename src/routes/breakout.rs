@@ -1,11 +1,17 @@
 use crate::{
     SharedState,
     domain::{
-        breakout::{Breakout, NewBreakout},
-        breakout_channel::BreakoutChannel,
-        user::{UpdateUser, User},
+        breakout::{Breakout, NewBreakout, UpdateBreakout},
+        breakout_channel::{BreakoutChannel, Countdown},
+        deck::Deck,
+        membership::ParticipantRole,
+        pert::{EstimationMode, ThreePoint},
+        round_timer::{RoundTimer, TimerExpiry},
+        user::{DisplayName, UpdateUser, User},
     },
+    error::AppError,
     extract::{breakout::BreakoutRoom, breakout_user::BreakoutUser},
+    jobs,
     routes::SharedContext,
     util::htmx::HTMX,
 };
@@ -14,17 +20,21 @@ use askama_web::WebTemplate;
 use axum::extract::ws::Message;
 use axum::{
     Form, Router,
-    extract::{Path, State, WebSocketUpgrade, ws::WebSocket},
-    http::HeaderMap,
-    response::{IntoResponse, Redirect},
-    routing::{get, patch, put},
+    extract::{Path, Query, State, WebSocketUpgrade, ws::WebSocket},
+    http::{
+        HeaderMap, StatusCode,
+        header::{CACHE_CONTROL, CONTENT_TYPE},
+    },
+    response::{IntoResponse, Response},
+    routing::{get, patch, post, put},
 };
 use axum_extra::extract::{
     CookieJar,
     cookie::{Cookie, SameSite},
 };
 use futures_util::{sink::SinkExt, stream::StreamExt};
-use reqwest::StatusCode;
+use log::error;
+use qrcode::{QrCode, render::svg};
 use serde::Deserialize;
 use time::Duration;
 
@@ -35,12 +45,32 @@ pub fn routes() -> Router<SharedState> {
         .route("/breakout/{lookup_id}/ws", get(breakout_ws))
         .route("/breakout/{lookup_id}/user", patch(update_user))
         .route("/breakout/{lookup_id}/user", get(user_form))
+        .route("/breakout/{lookup_id}/settings", get(settings_form))
+        .route("/breakout/{lookup_id}/settings", put(update_settings))
+        .route(
+            "/breakout/{lookup_id}/participants/{public_id}/role",
+            post(set_participant_role),
+        )
+        .route("/breakout/{lookup_id}/qr", get(qr_modal))
+        .route("/breakout/{lookup_id}/qr.svg", get(qr_code))
 }
 
 #[derive(Deserialize)]
 struct ClientMessage {
     action: String,
     vote: Option<String>,
+    /// A three-point estimate, sent instead of `vote` in that mode.
+    optimistic: Option<String>,
+    likely: Option<String>,
+    pessimistic: Option<String>,
+    seconds: Option<String>,
+}
+
+/// The private link to a breakout started from Slack carries the token that
+/// makes whoever started it the facilitator.
+#[derive(Deserialize)]
+struct ClaimQuery {
+    claim: Option<String>,
 }
 
 #[derive(Template, WebTemplate)]
@@ -48,10 +78,25 @@ struct ClientMessage {
 struct BreakoutTemplate {
     shared: SharedContext,
     breakout: Breakout,
+    is_facilitator: bool,
+    /// Passed on when joining, which is when the role is claimed.
+    claim: Option<String>,
+    deck: Deck,
 }
 impl BreakoutTemplate {
-    pub fn new(shared: SharedContext, breakout: Breakout) -> Self {
-        Self { shared, breakout }
+    pub fn new(
+        shared: SharedContext,
+        breakout: Breakout,
+        is_facilitator: bool,
+        claim: Option<String>,
+    ) -> Self {
+        Self {
+            shared,
+            breakout,
+            is_facilitator,
+            claim,
+            deck: Deck::default(),
+        }
     }
 }
 
@@ -59,40 +104,262 @@ impl BreakoutTemplate {
 #[template(path = "update_user.html")]
 struct UpdateUserTemplate {
     breakout: Breakout,
+    display_name: String,
+    observer: bool,
+    error: Option<String>,
 }
 
 #[derive(Deserialize)]
 struct UpdateUserForm {
     display_name: String,
+    observer: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RoleForm {
+    role: ParticipantRole,
+}
+
+#[derive(Template, WebTemplate)]
+#[template(path = "breakout_settings.html")]
+struct SettingsTemplate {
+    breakout: Breakout,
+    form: UpdateBreakout,
+    display_url: String,
+    intake_url: String,
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SettingsForm {
+    title: String,
+    description: String,
+    facilitator_only_controls: Option<String>,
+    auto_reveal: Option<String>,
+    auto_reveal_countdown: i64,
+    timer_expiry: TimerExpiry,
+    estimation_mode: EstimationMode,
+}
+
+#[derive(Template, WebTemplate)]
+#[template(path = "breakout_qr.html")]
+struct QrTemplate {
+    breakout: Breakout,
+    url: String,
 }
 
 async fn user_form(
+    State(state): State<SharedState>,
+    BreakoutRoom(breakout): BreakoutRoom,
+    BreakoutUser(user): BreakoutUser,
+) -> Result<UpdateUserTemplate, AppError> {
+    // Saving the form sets the role too, so it has to start out as it is.
+    let role = state
+        .membership_service
+        .find_role(breakout.id, user.id)
+        .await
+        .map_err(|e| AppError::internal(&format!("failed to find role of user {}", user.id), e))?;
+
+    Ok(UpdateUserTemplate {
+        breakout,
+        display_name: String::new(),
+        observer: role == ParticipantRole::Observer,
+        error: None,
+    })
+}
+
+async fn settings_form(
+    State(state): State<SharedState>,
+    BreakoutRoom(breakout): BreakoutRoom,
+    BreakoutUser(user): BreakoutUser,
+) -> Result<SettingsTemplate, AppError> {
+    if !breakout.is_facilitator(&user) {
+        return Err(forbidden_settings());
+    }
+
+    Ok(SettingsTemplate {
+        form: UpdateBreakout::from(&breakout),
+        display_url: state.app_info.display_url(&breakout.display_token),
+        intake_url: state.app_info.intake_url(&breakout.intake_token),
+        breakout,
+        error: None,
+    })
+}
+
+async fn update_settings(
+    State(state): State<SharedState>,
+    BreakoutRoom(breakout): BreakoutRoom,
+    BreakoutUser(user): BreakoutUser,
+    Form(form): Form<SettingsForm>,
+) -> Result<Response, AppError> {
+    if !breakout.is_facilitator(&user) {
+        return Err(forbidden_settings());
+    }
+
+    let update = UpdateBreakout {
+        id: breakout.id,
+        title: form.title,
+        description: form.description,
+        facilitator_only_controls: form.facilitator_only_controls.is_some(),
+        auto_reveal: form.auto_reveal.is_some(),
+        auto_reveal_countdown: form.auto_reveal_countdown,
+        timer_expiry: form.timer_expiry,
+        estimation_mode: form.estimation_mode,
+    };
+    let update = match update.clone().validated() {
+        Ok(update) => update,
+        Err(AppError::Validation(error)) => {
+            return Ok(SettingsTemplate {
+                display_url: state.app_info.display_url(&breakout.display_token),
+                intake_url: state.app_info.intake_url(&breakout.intake_token),
+                breakout,
+                form: update,
+                error: Some(error),
+            }
+            .into_response());
+        }
+        Err(e) => return Err(e),
+    };
+
+    let breakout =
+        state.breakout_service.update(&update).await.map_err(|e| {
+            AppError::internal(&format!("failed to update breakout {}", update.id), e)
+        })?;
+
+    let mut channels = state.breakout_channels.lock().await;
+    BreakoutChannel::find_or_create(&mut channels, &state.room_events, &breakout.lookup_id)
+        .apply_settings(&breakout);
+
+    let mut headers = HeaderMap::new();
+    headers.insert("HX-Trigger", "closeModal".parse().unwrap());
+
+    Ok((StatusCode::OK, headers).into_response())
+}
+
+/// Lets the facilitator move someone between voting and observing.
+async fn set_participant_role(
+    State(state): State<SharedState>,
+    Path((_, public_id)): Path<(String, String)>,
+    BreakoutRoom(breakout): BreakoutRoom,
+    BreakoutUser(user): BreakoutUser,
+    Form(form): Form<RoleForm>,
+) -> Result<StatusCode, AppError> {
+    if !breakout.is_facilitator(&user) {
+        return Err(AppError::Forbidden(
+            "Only the facilitator can change who is observing.".to_string(),
+        ));
+    }
+
+    let participant = state
+        .user_service
+        .find_by_public_id(&public_id)
+        .await
+        .map_err(AppError::from)?;
+
+    state
+        .membership_service
+        .set_role(breakout.id, participant.id, form.role)
+        .await
+        .map_err(|e| {
+            AppError::internal(&format!("failed to set role of user {}", participant.id), e)
+        })?;
+
+    let mut channels = state.breakout_channels.lock().await;
+    let countdown =
+        BreakoutChannel::find_or_create(&mut channels, &state.room_events, &breakout.lookup_id)
+            .set_role(&participant.public_id, form.role);
+    run_countdown(&state, &breakout.lookup_id, countdown);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn forbidden_settings() -> AppError {
+    AppError::Forbidden("Only the facilitator can change this breakout's settings.".to_string())
+}
+
+async fn qr_modal(
+    State(state): State<SharedState>,
     BreakoutRoom(breakout): BreakoutRoom,
-    BreakoutUser(_): BreakoutUser,
-) -> UpdateUserTemplate {
-    UpdateUserTemplate { breakout }
+) -> QrTemplate {
+    QrTemplate {
+        url: state.app_info.breakout_url(&breakout.lookup_id),
+        breakout,
+    }
+}
+
+async fn qr_code(
+    State(state): State<SharedState>,
+    BreakoutRoom(breakout): BreakoutRoom,
+) -> Result<impl IntoResponse, AppError> {
+    let url = state.app_info.breakout_url(&breakout.lookup_id);
+    let svg = QrCode::new(url.as_bytes())
+        .map_err(|e| AppError::internal("failed to generate QR code", e))?
+        .render::<svg::Color>()
+        .min_dimensions(320, 320)
+        .dark_color(svg::Color("#222222"))
+        .light_color(svg::Color("#ffffff"))
+        .build();
+
+    Ok((
+        [
+            (CONTENT_TYPE, "image/svg+xml"),
+            (CACHE_CONTROL, "public, max-age=86400"),
+        ],
+        svg,
+    ))
 }
 
 async fn update_user(
     State(state): State<SharedState>,
     Path(lookup_id): Path<String>,
     BreakoutUser(user): BreakoutUser,
-    BreakoutRoom(_): BreakoutRoom,
+    BreakoutRoom(breakout): BreakoutRoom,
     cookies: CookieJar,
     Form(form): Form<UpdateUserForm>,
-) -> impl IntoResponse {
+) -> Result<Response, AppError> {
+    let display_name = match DisplayName::parse(&form.display_name) {
+        Ok(display_name) => display_name,
+        Err(AppError::Validation(error)) => {
+            return Ok(UpdateUserTemplate {
+                breakout,
+                display_name: form.display_name,
+                observer: form.observer.is_some(),
+                error: Some(error),
+            }
+            .into_response());
+        }
+        Err(e) => return Err(e),
+    };
+
+    let user = state
+        .user_service
+        .persist(user)
+        .await
+        .map_err(|e| AppError::internal("failed to create user", e))?;
     let mut user = UpdateUser::from(&user);
-    let mut channels = state.breakout_channels.lock().await;
-    let channel = BreakoutChannel::find_or_create(&mut channels, &lookup_id);
+    user.display_name = display_name.into_inner();
 
-    user.display_name = form.display_name;
+    let user = state
+        .user_service
+        .update(&user)
+        .await
+        .map_err(|e| AppError::internal(&format!("failed to update user {}", user.id), e))?;
 
-    let user = match state.user_service.update(&user).await {
-        Ok(user) => user,
-        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    let role = match form.observer {
+        Some(_) => ParticipantRole::Observer,
+        None => ParticipantRole::Voter,
     };
+    state
+        .membership_service
+        .set_role(breakout.id, user.id, role)
+        .await
+        .map_err(|e| AppError::internal(&format!("failed to set role of user {}", user.id), e))?;
 
+    let mut channels = state.breakout_channels.lock().await;
+    let channel = BreakoutChannel::find_or_create(&mut channels, &state.room_events, &lookup_id);
     channel.user_changed_name(&user);
+    let countdown = channel.set_role(&user.public_id, role);
+    run_countdown(&state, &lookup_id, countdown);
 
     let display_name_cookie = Cookie::build(("guess_rs_display_name", user.display_name.clone()))
         .path("/")
@@ -103,37 +370,32 @@ async fn update_user(
     let mut headers = HeaderMap::new();
     headers.insert("HX-Trigger", "closeModal".parse().unwrap());
 
-    (StatusCode::OK, headers, cookies).into_response()
+    Ok((StatusCode::OK, headers, cookies).into_response())
 }
 
 async fn breakout(
     State(state): State<SharedState>,
-    Path(lookup_id): Path<String>,
     BreakoutUser(user): BreakoutUser,
-    BreakoutRoom(_): BreakoutRoom,
+    BreakoutRoom(breakout): BreakoutRoom,
+    Query(query): Query<ClaimQuery>,
     cookies: CookieJar,
 ) -> impl IntoResponse {
-    let whoami_cookie = Cookie::build(("whoami", user.lookup_id.clone()))
-        .path("/")
-        .http_only(true)
-        .same_site(SameSite::None)
-        .max_age(Duration::days(365))
-        .secure(true);
     let display_name_cookie = Cookie::build(("guess_rs_display_name", user.display_name.clone()))
         .path("/")
         .same_site(SameSite::None)
         .max_age(Duration::days(365));
     let cookies = cookies.add(display_name_cookie);
-    let cookies = cookies.add(whoami_cookie);
 
-    match state.breakout_service.find_by_lookup_id(lookup_id).await {
-        Ok(breakout) => (
-            cookies,
-            BreakoutTemplate::new(SharedContext::new(&state.app_info, Some(user)), breakout),
-        )
-            .into_response(),
-        Err(_) => Redirect::to("/").into_response(),
-    }
+    let is_facilitator = breakout.is_facilitator(&user);
+    let claim = query
+        .claim
+        .filter(|claim| breakout.facilitator_token.as_ref() == Some(claim));
+    let shared = SharedContext::new(&state.app_info, Some(user)).with_subtitle(&breakout.title);
+
+    (
+        cookies,
+        BreakoutTemplate::new(shared, breakout, is_facilitator, claim),
+    )
 }
 
 async fn breakout_ws(
@@ -142,14 +404,70 @@ async fn breakout_ws(
     Path(_): Path<String>,
     BreakoutUser(user): BreakoutUser,
     BreakoutRoom(breakout): BreakoutRoom,
-) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_socket(socket, state, user, breakout))
+    Query(query): Query<ClaimQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    // Joining a breakout is what turns a guest into a real user.
+    let user = state
+        .user_service
+        .persist(user)
+        .await
+        .map_err(|e| AppError::internal("failed to create user", e))?;
+
+    Ok(ws.on_upgrade(move |socket| handle_socket(socket, state, user, breakout, query.claim)))
 }
 
-async fn handle_socket(socket: WebSocket, state: SharedState, user: User, breakout: Breakout) {
+async fn handle_socket(
+    socket: WebSocket,
+    state: SharedState,
+    mut user: User,
+    mut breakout: Breakout,
+    claim: Option<String>,
+) {
+    touch_membership(&state, &breakout, &user).await;
+
+    // Whoever joins a breakout that has lost its facilitator, or never had
+    // one, takes over, unless it's kept for whoever holds the token. Only
+    // joining counts, so link previews and crawlers that just fetch the
+    // page can't.
+    let mut claimed = false;
+    if breakout.facilitator_id.is_none() {
+        match state
+            .breakout_service
+            .claim_facilitator(breakout.id, user.id, claim.as_deref())
+            .await
+        {
+            Ok(Some(updated)) => {
+                breakout = updated;
+                claimed = true;
+            }
+            Ok(None) => {}
+            Err(e) => error!(
+                "Failed to claim facilitator of breakout {}: {e}",
+                breakout.id
+            ),
+        }
+    }
+
+    match state
+        .membership_service
+        .find_role(breakout.id, user.id)
+        .await
+    {
+        Ok(role) => user.role = role,
+        Err(e) => error!("Failed to find role of user {}: {e}", user.id),
+    }
+
+    let backlog = state.story_service.find_by_breakout(breakout.id).await;
+
     let tx = {
         let mut channels = state.breakout_channels.lock().await;
-        let channel = BreakoutChannel::find_or_create(&mut channels, &breakout.lookup_id);
+        let channel =
+            BreakoutChannel::find_or_create(&mut channels, &state.room_events, &breakout.lookup_id);
+        channel.apply_settings(&breakout);
+        match backlog {
+            Ok(backlog) => channel.set_backlog(backlog),
+            Err(e) => error!("Failed to load backlog of breakout {}: {e}", breakout.id),
+        }
         channel.add_user(&user);
         channel.tx.clone()
     };
@@ -157,12 +475,28 @@ async fn handle_socket(socket: WebSocket, state: SharedState, user: User, breako
     let mut rx = tx.subscribe();
     let (mut sender, mut receiver) = socket.split();
 
+    if claimed {
+        let _ = sender
+            .send(Message::Text(
+                "event: facilitator\ndata: you run this breakout\n\n".into(),
+            ))
+            .await;
+    }
+
     {
         let channels = state.breakout_channels.lock().await;
         if let Some(channel) = channels.get(&breakout.lookup_id) {
-            let _ = sender
-                .send(Message::Text(channel.voters_html().into()))
-                .await;
+            for html in [
+                channel.story_html(),
+                channel.timer_html(),
+                channel.voters_html(),
+                channel.backlog_html(),
+            ]
+            .into_iter()
+            .flatten()
+            {
+                let _ = sender.send(Message::Text(html.into())).await;
+            }
         }
     }
 
@@ -185,9 +519,10 @@ async fn handle_socket(socket: WebSocket, state: SharedState, user: User, breako
                         let mut channels = state_clone.breakout_channels.lock().await;
                         let channel = BreakoutChannel::find_or_create(
                             &mut channels,
+                            &state_clone.room_events,
                             &breakout_clone.lookup_id,
                         );
-                        handle_event(&event, &user_clone, channel);
+                        handle_event(&event, &user_clone, channel, &state_clone);
                     }
                 }
                 Message::Close(_) => break,
@@ -201,26 +536,124 @@ async fn handle_socket(socket: WebSocket, state: SharedState, user: User, breako
         _ = recv_task => {},
     }
 
-    let mut channels = state.breakout_channels.lock().await;
-    if let Some(channel) = channels.get_mut(&breakout.lookup_id) {
-        channel.remove_user(&user.lookup_id);
+    {
+        let mut channels = state.breakout_channels.lock().await;
+        if let Some(departure) = channels
+            .get_mut(&breakout.lookup_id)
+            .map(|channel| channel.remove_user(&user.lookup_id))
+        {
+            run_countdown(&state, &breakout.lookup_id, departure.countdown);
+            if let Some(generation) = departure.session_end {
+                tokio::spawn(jobs::session_end::run(
+                    state.clone(),
+                    breakout.lookup_id.clone(),
+                    generation,
+                ));
+            }
+        }
     }
+
+    touch_membership(&state, &breakout, &user).await;
 }
 
-async fn create_breakout(State(state): State<SharedState>) -> impl IntoResponse {
-    let breakout = NewBreakout::default();
-    match state.breakout_service.create(&breakout).await {
-        Ok(breakout) => {
-            HTMX::redirect(&format!("/breakout/{}", breakout.lookup_id)).into_response()
-        }
-        Err(_) => HTMX::refresh().into_response(),
+/// Counts down to an automatic reveal, if one was started.
+fn run_countdown(state: &SharedState, lookup_id: &str, countdown: Option<Countdown>) {
+    if let Some(countdown) = countdown {
+        tokio::spawn(jobs::auto_reveal::run(
+            state.breakout_channels.clone(),
+            lookup_id.to_string(),
+            countdown,
+        ));
+    }
+}
+
+async fn touch_membership(state: &SharedState, breakout: &Breakout, user: &User) {
+    if let Err(e) = state.membership_service.touch(breakout.id, user.id).await {
+        error!(
+            "Failed to record membership of user {} in breakout {}: {e}",
+            user.id, breakout.id
+        );
     }
 }
 
-fn handle_event(event: &ClientMessage, user: &User, channel: &mut BreakoutChannel) {
+async fn create_breakout(
+    State(state): State<SharedState>,
+    BreakoutUser(user): BreakoutUser,
+) -> Result<impl IntoResponse, AppError> {
+    let user = state
+        .user_service
+        .persist(user)
+        .await
+        .map_err(|e| AppError::internal("failed to create user", e))?;
+    let breakout = NewBreakout {
+        facilitator_id: Some(user.id),
+        ..NewBreakout::default()
+    };
+    let breakout = state
+        .breakout_service
+        .create(&breakout)
+        .await
+        .map_err(|e| AppError::internal("failed to create breakout", e))?;
+
+    Ok(HTMX::redirect(&format!("/breakout/{}", breakout.lookup_id)).into_response())
+}
+
+fn handle_event(
+    event: &ClientMessage,
+    user: &User,
+    channel: &mut BreakoutChannel,
+    state: &SharedState,
+) {
     match event.action.as_str() {
-        "toggle_votes" => channel.toggle_votes(),
-        "vote" => channel.vote(&user.lookup_id, &event.vote),
+        "toggle_votes" if channel.settings.can_control(user) => channel.toggle_votes(),
+        "cancel_reveal" if channel.settings.can_control(user) => channel.cancel_countdown(),
+        "start_talking_stick" if channel.settings.is_facilitator(user) => {
+            channel.start_talking_stick()
+        }
+        "next_speaker" if channel.settings.is_facilitator(user) => channel.next_speaker(),
+        "end_talking_stick" if channel.settings.is_facilitator(user) => channel.end_talking_stick(),
+        "vote" => {
+            let vote = match (&event.optimistic, &event.likely, &event.pessimistic) {
+                (Some(optimistic), Some(likely), Some(pessimistic)) => {
+                    ThreePoint::vote(optimistic, likely, pessimistic)
+                }
+                _ => event.vote.clone(),
+            };
+            let countdown = channel.vote(&user.lookup_id, &vote);
+            run_countdown(state, &channel.lookup_id, countdown);
+        }
+        "start_timer" | "pause_timer" | "resume_timer" | "extend_timer" | "stop_timer"
+            if channel.settings.is_facilitator(user) =>
+        {
+            if let Some(timer) = handle_timer_event(event, channel) {
+                tokio::spawn(jobs::round_timer::run(
+                    state.breakout_channels.clone(),
+                    channel.lookup_id.clone(),
+                    timer,
+                ));
+            }
+        }
         _ => {}
     }
 }
+
+/// Applies a facilitator's change to the round timer, returning the timer
+/// if it needs a new task to tick it.
+fn handle_timer_event(event: &ClientMessage, channel: &mut BreakoutChannel) -> Option<RoundTimer> {
+    let seconds = event.seconds.as_deref().and_then(|s| s.parse().ok());
+
+    match event.action.as_str() {
+        "start_timer" => Some(channel.start_timer(seconds.unwrap_or(120))),
+        "pause_timer" => {
+            channel.pause_timer();
+            None
+        }
+        "resume_timer" => channel.resume_timer(),
+        "extend_timer" => channel.extend_timer(seconds.unwrap_or(60)),
+        "stop_timer" => {
+            channel.stop_timer();
+            None
+        }
+        _ => None,
+    }
+}
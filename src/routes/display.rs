@@ -0,0 +1,149 @@
+use askama::Template;
+use askama_web::WebTemplate;
+use axum::{
+    Router,
+    extract::{
+        Path, State, WebSocketUpgrade,
+        ws::{Message, WebSocket},
+    },
+    response::IntoResponse,
+    routing::get,
+};
+use futures_util::{sink::SinkExt, stream::StreamExt};
+use log::error;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::{
+    SharedState,
+    domain::{breakout::Breakout, breakout_channel::BreakoutChannel},
+    error::AppError,
+    extract::breakout::BreakoutRoom,
+    routes::SharedContext,
+};
+
+pub fn routes() -> Router<SharedState> {
+    Router::new()
+        .route("/breakout/{lookup_id}/display", get(display_by_lookup_id))
+        .route("/display/{token}", get(display_by_token))
+        .route("/display/{token}/ws", get(display_ws))
+}
+
+#[derive(Template, WebTemplate)]
+#[template(path = "breakout_display.html")]
+struct DisplayTemplate {
+    shared: SharedContext,
+    breakout: Breakout,
+}
+
+async fn display_by_lookup_id(
+    State(state): State<SharedState>,
+    BreakoutRoom(breakout): BreakoutRoom,
+) -> DisplayTemplate {
+    DisplayTemplate {
+        shared: SharedContext::new(&state.app_info, None).with_subtitle(&breakout.title),
+        breakout,
+    }
+}
+
+/// The read-only share link, which works without knowing how to join.
+async fn display_by_token(
+    State(state): State<SharedState>,
+    Path(token): Path<String>,
+) -> Result<DisplayTemplate, AppError> {
+    let breakout = find_by_token(&state, &token).await?;
+
+    Ok(DisplayTemplate {
+        shared: SharedContext::new(&state.app_info, None).with_subtitle(&breakout.title),
+        breakout,
+    })
+}
+
+async fn display_ws(
+    ws: WebSocketUpgrade,
+    State(state): State<SharedState>,
+    Path(token): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let breakout = find_by_token(&state, &token).await?;
+
+    Ok(ws.on_upgrade(move |socket| handle_socket(socket, state, breakout)))
+}
+
+async fn find_by_token(state: &SharedState, token: &str) -> Result<Breakout, AppError> {
+    state
+        .breakout_service
+        .find_by_display_token(token)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => {
+                AppError::NotFound("This display link doesn't exist.".to_string())
+            }
+            e => AppError::from(e),
+        })
+}
+
+/// Watches the breakout without joining it. Whatever the participants are
+/// sent, the display re-renders its own board from the channel instead.
+async fn handle_socket(socket: WebSocket, state: SharedState, breakout: Breakout) {
+    let backlog = state.story_service.find_by_breakout(breakout.id).await;
+
+    let mut rx = {
+        let mut channels = state.breakout_channels.lock().await;
+        let channel =
+            BreakoutChannel::find_or_create(&mut channels, &state.room_events, &breakout.lookup_id);
+        channel.apply_settings(&breakout);
+        match backlog {
+            Ok(backlog) => channel.set_backlog(backlog),
+            Err(e) => error!("Failed to load backlog of breakout {}: {e}", breakout.id),
+        }
+        channel.tx.subscribe()
+    };
+
+    let (mut sender, mut receiver) = socket.split();
+
+    if let Some(html) = display_html(&state, &breakout.lookup_id).await {
+        let _ = sender.send(Message::Text(html.into())).await;
+    }
+
+    let send_task = tokio::spawn(async move {
+        loop {
+            match rx.recv().await {
+                Ok(msg) if msg.starts_with("event:") => continue,
+                Ok(_) | Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => break,
+            }
+
+            let Some(html) = display_html(&state, &breakout.lookup_id).await else {
+                continue;
+            };
+            if sender.send(Message::Text(html.into())).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let recv_task = tokio::spawn(async move {
+        while let Some(Ok(msg)) = receiver.next().await {
+            if let Message::Close(_) = msg {
+                break;
+            }
+        }
+    });
+
+    tokio::select! {
+        _ = send_task => {},
+        _ = recv_task => {},
+    }
+}
+
+async fn display_html(state: &SharedState, lookup_id: &str) -> Option<String> {
+    let channels = state.breakout_channels.lock().await;
+
+    match channels.get(lookup_id).map(|c| c.display_html()) {
+        Some(Ok(html)) => Some(html),
+        Some(Err(e)) => {
+            error!("Failed to render display for {lookup_id}: {e}");
+            None
+        }
+        None => None,
+    }
+}
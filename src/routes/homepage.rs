@@ -1,22 +1,77 @@
 use crate::SharedState;
+use crate::domain::membership::RecentBreakout;
+use crate::error::AppError;
+use crate::extract::{BaseUser, breakout::BreakoutRoom, breakout_user::BreakoutUser};
 use crate::routes::SharedContext;
 
 use askama::Template;
 use askama_web::WebTemplate;
-use axum::{Router, extract::State, routing::get};
+use axum::{
+    Router,
+    extract::State,
+    routing::{get, post},
+};
+use log::error;
 
 pub fn routes() -> Router<SharedState> {
-    Router::new().route("/", get(homepage))
+    Router::new()
+        .route("/", get(homepage))
+        .route("/breakout/{lookup_id}/pin", post(toggle_pin))
 }
 
 #[derive(Template, WebTemplate)]
 #[template(path = "homepage.html")]
 struct HomepageTemplate {
     shared: SharedContext,
+    recent: Vec<RecentBreakout>,
 }
 
-async fn homepage(State(state): State<SharedState>) -> HomepageTemplate {
+#[derive(Template, WebTemplate)]
+#[template(path = "recent_breakouts.html")]
+struct RecentBreakoutsTemplate {
+    recent: Vec<RecentBreakout>,
+}
+
+async fn homepage(State(state): State<SharedState>, user: BaseUser) -> HomepageTemplate {
+    let user = match user {
+        BaseUser::User(user) => Some(user),
+        _ => None,
+    };
+
+    let recent = match &user {
+        Some(user) => state
+            .membership_service
+            .find_recent(user.id)
+            .await
+            .unwrap_or_else(|e| {
+                error!("Failed to find recent breakouts for user {}: {e}", user.id);
+                vec![]
+            }),
+        None => vec![],
+    };
+
     HomepageTemplate {
-        shared: SharedContext::new(&state.app_info, None),
+        shared: SharedContext::new(&state.app_info, user),
+        recent,
     }
 }
+
+async fn toggle_pin(
+    State(state): State<SharedState>,
+    BreakoutRoom(breakout): BreakoutRoom,
+    BreakoutUser(user): BreakoutUser,
+) -> Result<RecentBreakoutsTemplate, AppError> {
+    state
+        .membership_service
+        .toggle_pin(breakout.id, user.id)
+        .await
+        .map_err(|e| AppError::internal("failed to pin breakout", e))?;
+
+    let recent = state
+        .membership_service
+        .find_recent(user.id)
+        .await
+        .map_err(|e| AppError::internal("failed to find recent breakouts", e))?;
+
+    Ok(RecentBreakoutsTemplate { recent })
+}
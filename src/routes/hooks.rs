@@ -0,0 +1,111 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State, rejection::JsonRejection},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::post,
+};
+use log::error;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    SharedState,
+    domain::{breakout_channel::BreakoutChannel, story::IncomingStory},
+    error::AppError,
+};
+
+/// Endpoints for other tools rather than people, so they answer in JSON and
+/// sit outside the identity and error page middleware.
+pub fn routes() -> Router<SharedState> {
+    Router::new().route("/hooks/breakout/{token}/stories", post(push_story))
+}
+
+#[derive(Deserialize)]
+struct StoryPayload {
+    title: String,
+    link: Option<String>,
+    description: Option<String>,
+    external_key: Option<String>,
+}
+
+#[derive(Serialize)]
+struct StoryResponse {
+    id: i64,
+    title: String,
+    external_key: Option<String>,
+    /// False when a story with the same external key was already there.
+    created: bool,
+}
+
+async fn push_story(
+    State(state): State<SharedState>,
+    Path(token): Path<String>,
+    payload: Result<Json<StoryPayload>, JsonRejection>,
+) -> Response {
+    let payload = match payload {
+        Ok(Json(payload)) => payload,
+        Err(rejection) => return json_error(rejection.status(), &rejection.body_text()),
+    };
+
+    match add_story(&state, &token, payload).await {
+        Ok(response) if response.created => (StatusCode::CREATED, Json(response)).into_response(),
+        Ok(response) => (StatusCode::OK, Json(response)).into_response(),
+        Err(e) => {
+            if let AppError::Internal(context) = &e {
+                error!("{context}");
+            }
+            json_error(e.status(), e.message())
+        }
+    }
+}
+
+async fn add_story(
+    state: &SharedState,
+    token: &str,
+    payload: StoryPayload,
+) -> Result<StoryResponse, AppError> {
+    let breakout = state
+        .breakout_service
+        .find_by_intake_token(token)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => {
+                AppError::NotFound("This intake URL doesn't exist.".to_string())
+            }
+            e => AppError::from(e),
+        })?;
+
+    let story = IncomingStory {
+        breakout_id: breakout.id,
+        title: payload.title,
+        link: payload.link,
+        description: payload.description,
+        external_key: payload.external_key,
+    }
+    .validated()?;
+    let (story, created) = state.story_service.push(&story).await?;
+
+    if created {
+        let backlog = state
+            .story_service
+            .find_by_breakout(breakout.id)
+            .await
+            .map_err(|e| {
+                AppError::internal(&format!("failed to load backlog of {}", breakout.id), e)
+            })?;
+        let mut channels = state.breakout_channels.lock().await;
+        BreakoutChannel::find_or_create(&mut channels, &state.room_events, &breakout.lookup_id)
+            .set_backlog(backlog);
+    }
+
+    Ok(StoryResponse {
+        id: story.id,
+        title: story.title,
+        external_key: story.external_key,
+        created,
+    })
+}
+
+fn json_error(status: StatusCode, message: &str) -> Response {
+    (status, Json(serde_json::json!({ "error": message }))).into_response()
+}
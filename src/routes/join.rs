@@ -0,0 +1,42 @@
+use axum::{
+    Router,
+    extract::{Path, Query, State},
+    response::Redirect,
+    routing::get,
+};
+use serde::Deserialize;
+
+use crate::{SharedState, domain::join_code, error::AppError};
+
+pub fn routes() -> Router<SharedState> {
+    Router::new()
+        .route("/j/{code}", get(join_by_code))
+        .route("/join", get(join_form))
+}
+
+#[derive(Deserialize)]
+struct JoinQuery {
+    code: String,
+}
+
+async fn join_by_code(
+    State(state): State<SharedState>,
+    Path(code): Path<String>,
+) -> Result<Redirect, AppError> {
+    let code = join_code::normalize(&code);
+
+    match state.breakout_service.find_by_code(&code).await {
+        Ok(breakout) => Ok(Redirect::to(&format!("/breakout/{}", breakout.lookup_id))),
+        Err(sqlx::Error::RowNotFound) => Err(AppError::NotFound(format!(
+            "No breakout matches the code \"{code}\". Double check it and try again."
+        ))),
+        Err(e) => Err(AppError::internal("failed to find breakout by code", e)),
+    }
+}
+
+async fn join_form(
+    state: State<SharedState>,
+    Query(query): Query<JoinQuery>,
+) -> Result<Redirect, AppError> {
+    join_by_code(state, Path(query.code)).await
+}
@@ -1,17 +1,39 @@
-use crate::{AppInfo, domain::user::User};
+use crate::{AppInfo, domain::user::User, error::AppError};
 
 pub mod breakout;
+pub mod display;
 pub mod homepage;
+pub mod hooks;
+pub mod join;
+pub mod notifications;
+pub mod report;
+pub mod slack;
+pub mod story;
+pub mod team;
+pub mod tracker;
+pub mod webhook;
 
 pub struct SharedContext {
     pub app_info: AppInfo,
     pub user: Option<User>,
+    /// Shown next to the brand in the navbar.
+    pub subtitle: Option<String>,
 }
 impl SharedContext {
     pub fn new(app_info: &AppInfo, user: Option<User>) -> Self {
         Self {
             app_info: app_info.clone(),
             user,
+            subtitle: None,
         }
     }
+
+    pub fn with_subtitle(mut self, subtitle: &str) -> Self {
+        self.subtitle = Some(subtitle.to_string());
+        self
+    }
+}
+
+pub async fn not_found() -> AppError {
+    AppError::NotFound("This page doesn't exist.".to_string())
 }
@@ -0,0 +1,109 @@
+use askama::Template;
+use askama_web::WebTemplate;
+use axum::{
+    Form, Router,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, put},
+};
+use serde::Deserialize;
+
+use crate::{
+    SharedState,
+    domain::{breakout::Breakout, notification::UpdateNotifications},
+    error::AppError,
+    extract::{breakout::BreakoutRoom, breakout_user::BreakoutUser},
+};
+
+pub fn routes() -> Router<SharedState> {
+    Router::new()
+        .route(
+            "/breakout/{lookup_id}/notifications",
+            get(notifications_form),
+        )
+        .route(
+            "/breakout/{lookup_id}/notifications",
+            put(update_notifications),
+        )
+}
+
+#[derive(Template, WebTemplate)]
+#[template(path = "breakout_notifications.html")]
+struct NotificationsTemplate {
+    breakout: Breakout,
+    form: UpdateNotifications,
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct NotificationsForm {
+    slack_webhook_url: String,
+    teams_webhook_url: String,
+    discord_webhook_url: String,
+}
+
+async fn notifications_form(
+    BreakoutRoom(breakout): BreakoutRoom,
+    BreakoutUser(user): BreakoutUser,
+) -> Result<NotificationsTemplate, AppError> {
+    if !breakout.is_facilitator(&user) {
+        return Err(forbidden_notifications());
+    }
+
+    Ok(NotificationsTemplate {
+        form: UpdateNotifications::from(&breakout),
+        breakout,
+        error: None,
+    })
+}
+
+async fn update_notifications(
+    State(state): State<SharedState>,
+    BreakoutRoom(breakout): BreakoutRoom,
+    BreakoutUser(user): BreakoutUser,
+    Form(form): Form<NotificationsForm>,
+) -> Result<Response, AppError> {
+    if !breakout.is_facilitator(&user) {
+        return Err(forbidden_notifications());
+    }
+
+    let update = UpdateNotifications {
+        id: breakout.id,
+        slack_webhook_url: Some(form.slack_webhook_url),
+        teams_webhook_url: Some(form.teams_webhook_url),
+        discord_webhook_url: Some(form.discord_webhook_url),
+    };
+    let update = match update.clone().validated() {
+        Ok(update) => update,
+        Err(AppError::Validation(error)) => {
+            return Ok(NotificationsTemplate {
+                breakout,
+                form: update,
+                error: Some(error),
+            }
+            .into_response());
+        }
+        Err(e) => return Err(e),
+    };
+
+    state
+        .breakout_service
+        .update_notifications(&update)
+        .await
+        .map_err(|e| {
+            AppError::internal(
+                &format!("failed to update notifications of {}", update.id),
+                e,
+            )
+        })?;
+
+    let mut headers = HeaderMap::new();
+    headers.insert("HX-Trigger", "closeModal".parse().unwrap());
+
+    Ok((StatusCode::OK, headers).into_response())
+}
+
+fn forbidden_notifications() -> AppError {
+    AppError::Forbidden("Only the facilitator can change chat notifications.".to_string())
+}
@@ -0,0 +1,80 @@
+use askama::Template;
+use askama_web::WebTemplate;
+use axum::{
+    Router,
+    extract::{Query, State},
+    routing::get,
+};
+use serde::Deserialize;
+
+use crate::{
+    SharedState,
+    domain::{
+        breakout::Breakout,
+        session_report::{SavedSession, SessionReport},
+        team::Team,
+    },
+    error::AppError,
+    extract::breakout::BreakoutRoom,
+    routes::SharedContext,
+};
+
+pub fn routes() -> Router<SharedState> {
+    Router::new().route("/breakout/{lookup_id}/report", get(report))
+}
+
+#[derive(Template, WebTemplate)]
+#[template(path = "breakout_report.html")]
+struct ReportTemplate {
+    shared: SharedContext,
+    breakout: Breakout,
+    /// Every finished session, for picking another one to read.
+    sessions: Vec<SavedSession>,
+    /// None until the breakout has finished a session.
+    report: Option<SessionReport>,
+    team: Option<Team>,
+}
+
+#[derive(Deserialize)]
+struct ReportQuery {
+    session: Option<i64>,
+}
+
+/// A finished session's report, the latest unless another one is asked
+/// for. Like the breakout itself, anyone with the link can read it.
+async fn report(
+    State(state): State<SharedState>,
+    BreakoutRoom(breakout): BreakoutRoom,
+    Query(query): Query<ReportQuery>,
+) -> Result<ReportTemplate, AppError> {
+    let sessions = state.session_service.find_by_breakout(breakout.id).await?;
+
+    let report = match query.session.or(sessions.first().map(|s| s.id)) {
+        Some(id) => Some(
+            state
+                .session_service
+                .report(breakout.id, id)
+                .await
+                .map_err(|e| match e {
+                    sqlx::Error::RowNotFound => {
+                        AppError::NotFound("This session report doesn't exist.".to_string())
+                    }
+                    e => AppError::internal(&format!("failed to load session {id}"), e),
+                })?,
+        ),
+        None => None,
+    };
+
+    let team = match breakout.team_id {
+        Some(team_id) => Some(state.team_service.find(team_id).await?),
+        None => None,
+    };
+
+    Ok(ReportTemplate {
+        shared: SharedContext::new(&state.app_info, None).with_subtitle(&breakout.title),
+        breakout,
+        sessions,
+        report,
+        team,
+    })
+}
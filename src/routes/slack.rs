@@ -0,0 +1,131 @@
+use axum::{
+    Json, Router,
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::post,
+};
+use log::error;
+use serde::Deserialize;
+
+use crate::{
+    SharedState, domain::breakout::NewBreakout, error::AppError, infrastructure::chat::slack,
+};
+
+/// Slack talks to these directly, so they sit outside the identity and
+/// error page middleware like the other hooks.
+pub fn routes() -> Router<SharedState> {
+    Router::new().route("/hooks/slack/commands", post(slash_command))
+}
+
+/// The parts of a slash command we use; Slack sends many more.
+#[derive(Deserialize)]
+struct SlashCommand {
+    command: String,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    user_name: String,
+    /// Where to send more messages about the command.
+    #[serde(default)]
+    response_url: String,
+}
+
+/// Handles `/guess [title]`, starting a breakout, handing whoever ran it the
+/// link to run it, and sharing the link to join with the channel.
+async fn slash_command(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+    };
+    let body = String::from_utf8_lossy(&body);
+
+    if let Err(e) = state.slack_service.verify(
+        header("X-Slack-Request-Timestamp"),
+        header("X-Slack-Signature"),
+        &body,
+    ) {
+        return (e.status(), e.message().to_string()).into_response();
+    }
+
+    let Ok(command) = serde_urlencoded::from_str::<SlashCommand>(&body) else {
+        return (StatusCode::BAD_REQUEST, "Malformed slash command.").into_response();
+    };
+
+    let text = command.text.trim();
+    if text == "help" {
+        return Json(slack::ephemeral(&format!(
+            "Use `{0}` to start a breakout, or `{0} Sprint 42 planning` to give it a title.",
+            command.command
+        )))
+        .into_response();
+    }
+
+    match start_breakout(&state, text, &command.user_name).await {
+        Ok((reply, announcement)) => {
+            let state = state.clone();
+            tokio::spawn(async move {
+                if let Err(e) = state
+                    .slack_service
+                    .respond(&command.response_url, &announcement)
+                    .await
+                {
+                    error!("Failed to share a breakout started from Slack: {e}");
+                }
+            });
+            Json(reply).into_response()
+        }
+        Err(AppError::Internal(context)) => {
+            error!("{context}");
+            Json(slack::ephemeral(
+                "We ran into a problem starting the breakout. Please try again.",
+            ))
+            .into_response()
+        }
+        Err(e) => Json(slack::ephemeral(e.message())).into_response(),
+    }
+}
+
+/// Starts a breakout without a facilitator, as whoever ran the command
+/// isn't known to us yet. They claim the role when they join with the
+/// facilitator token only their reply links to. Returns that reply and the
+/// message for the channel.
+async fn start_breakout(
+    state: &SharedState,
+    title: &str,
+    started_by: &str,
+) -> Result<(serde_json::Value, serde_json::Value), AppError> {
+    let breakout = match title.is_empty() {
+        true => NewBreakout::default(),
+        false => NewBreakout::titled(title)?,
+    };
+    let token = uuid::Uuid::new_v4().simple().to_string();
+    let breakout = state
+        .breakout_service
+        .create(&NewBreakout {
+            started_in_slack: true,
+            facilitator_token: Some(token.clone()),
+            ..breakout
+        })
+        .await
+        .map_err(|e| AppError::internal("failed to create breakout", e))?;
+
+    let url = state.app_info.breakout_url(&breakout.lookup_id);
+    let claim_url = format!("{url}?claim={token}");
+    let started_by = match started_by.is_empty() {
+        true => "Someone",
+        false => started_by,
+    };
+
+    Ok((
+        slack::breakout_ready(&breakout, &claim_url),
+        slack::breakout_started(&breakout, &url, started_by),
+    ))
+}
@@ -0,0 +1,332 @@
+use askama::Template;
+use askama_web::WebTemplate;
+use axum::{
+    Form, Router,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post, put},
+};
+use serde::Deserialize;
+
+use crate::{
+    SharedState,
+    domain::{
+        breakout::Breakout,
+        breakout_channel::BreakoutChannel,
+        story::{AcceptEstimate, NewStory, RecordActual, Story},
+    },
+    error::AppError,
+    extract::{breakout::BreakoutRoom, breakout_user::BreakoutUser},
+    jobs,
+};
+
+pub fn routes() -> Router<SharedState> {
+    Router::new()
+        .route("/breakout/{lookup_id}/stories", post(add_story))
+        .route(
+            "/breakout/{lookup_id}/stories/{story_id}/select",
+            post(select_story),
+        )
+        .route("/breakout/{lookup_id}/estimate", get(estimate_form))
+        .route("/breakout/{lookup_id}/estimate", post(accept_estimate))
+        .route(
+            "/breakout/{lookup_id}/stories/{story_id}/actual",
+            get(actual_form),
+        )
+        .route(
+            "/breakout/{lookup_id}/stories/{story_id}/actual",
+            put(record_actual),
+        )
+}
+
+#[derive(Deserialize)]
+struct StoryForm {
+    title: String,
+}
+
+#[derive(Deserialize)]
+struct EstimateForm {
+    story_id: i64,
+    estimate: String,
+    reason: String,
+}
+
+#[derive(Deserialize)]
+struct ActualForm {
+    actual: String,
+}
+
+#[derive(Template, WebTemplate)]
+#[template(path = "record_actual.html")]
+struct RecordActualTemplate {
+    breakout: Breakout,
+    story: Story,
+    actual: String,
+    error: Option<String>,
+}
+
+#[derive(Template, WebTemplate)]
+#[template(path = "accept_estimate.html")]
+struct AcceptEstimateTemplate {
+    breakout: Breakout,
+    story: Story,
+    form: AcceptEstimate,
+    error: Option<String>,
+}
+
+async fn add_story(
+    State(state): State<SharedState>,
+    BreakoutRoom(breakout): BreakoutRoom,
+    BreakoutUser(user): BreakoutUser,
+    Form(form): Form<StoryForm>,
+) -> Result<StatusCode, AppError> {
+    if !breakout.can_control(&user) {
+        return Err(forbidden_backlog());
+    }
+
+    let story = NewStory::parse(breakout.id, &form.title)?;
+    state
+        .story_service
+        .create(&story)
+        .await
+        .map_err(|e| AppError::internal(&format!("failed to add story to {}", breakout.id), e))?;
+
+    let backlog = find_backlog(&state, &breakout).await?;
+    let mut channels = state.breakout_channels.lock().await;
+    BreakoutChannel::find_or_create(&mut channels, &state.room_events, &breakout.lookup_id)
+        .set_backlog(backlog);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn select_story(
+    State(state): State<SharedState>,
+    Path((_, story_id)): Path<(String, i64)>,
+    BreakoutRoom(breakout): BreakoutRoom,
+    BreakoutUser(user): BreakoutUser,
+) -> Result<StatusCode, AppError> {
+    if !breakout.can_control(&user) {
+        return Err(forbidden_backlog());
+    }
+
+    let backlog = find_backlog(&state, &breakout).await?;
+    let mut channels = state.breakout_channels.lock().await;
+    let channel =
+        BreakoutChannel::find_or_create(&mut channels, &state.room_events, &breakout.lookup_id);
+    if channel.backlog.len() != backlog.len() {
+        channel.set_backlog(backlog);
+    }
+    channel.select_story(story_id);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn estimate_form(
+    State(state): State<SharedState>,
+    BreakoutRoom(breakout): BreakoutRoom,
+    BreakoutUser(user): BreakoutUser,
+) -> Result<AcceptEstimateTemplate, AppError> {
+    if !breakout.is_facilitator(&user) {
+        return Err(forbidden_estimate());
+    }
+
+    let (story, suggested) = {
+        let channels = state.breakout_channels.lock().await;
+        let channel = channels.get(&breakout.lookup_id);
+
+        (
+            channel.and_then(|c| c.story().cloned()),
+            channel.and_then(|c| c.suggested_estimate()),
+        )
+    };
+    let story = story.ok_or_else(nothing_to_estimate)?;
+
+    Ok(AcceptEstimateTemplate {
+        breakout,
+        form: AcceptEstimate {
+            story_id: story.id,
+            estimate: suggested.clone().unwrap_or_default(),
+            reason: String::new(),
+            suggested,
+        },
+        story,
+        error: None,
+    })
+}
+
+async fn accept_estimate(
+    State(state): State<SharedState>,
+    BreakoutRoom(breakout): BreakoutRoom,
+    BreakoutUser(user): BreakoutUser,
+    Form(form): Form<EstimateForm>,
+) -> Result<Response, AppError> {
+    if !breakout.is_facilitator(&user) {
+        return Err(forbidden_estimate());
+    }
+
+    let (story, suggested) = {
+        let channels = state.breakout_channels.lock().await;
+        let channel = channels.get(&breakout.lookup_id);
+
+        (
+            channel.and_then(|c| c.backlog.iter().find(|s| s.id == form.story_id).cloned()),
+            channel.and_then(|c| c.suggested_estimate()),
+        )
+    };
+    let story = story.ok_or_else(nothing_to_estimate)?;
+
+    let estimate = AcceptEstimate {
+        story_id: story.id,
+        estimate: form.estimate,
+        reason: form.reason,
+        suggested,
+    };
+    let estimate = match estimate.clone().validated() {
+        Ok(estimate) => estimate,
+        Err(AppError::Validation(error)) => {
+            return Ok(AcceptEstimateTemplate {
+                breakout,
+                story,
+                form: estimate,
+                error: Some(error),
+            }
+            .into_response());
+        }
+        Err(e) => return Err(e),
+    };
+
+    let story = state
+        .story_service
+        .accept_estimate(breakout.id, &estimate)
+        .await
+        .map_err(|e| AppError::internal(&format!("failed to estimate story {}", story.id), e))?;
+
+    let backlog = find_backlog(&state, &breakout).await?;
+    {
+        let mut channels = state.breakout_channels.lock().await;
+        BreakoutChannel::find_or_create(&mut channels, &state.room_events, &breakout.lookup_id)
+            .estimate_accepted(&story, backlog);
+    }
+
+    if story.external_key.is_some() {
+        tokio::spawn(jobs::tracker_sync::run(state.clone(), breakout, story));
+    }
+
+    let mut headers = HeaderMap::new();
+    headers.insert("HX-Trigger", "closeModal".parse().unwrap());
+
+    Ok((StatusCode::OK, headers).into_response())
+}
+
+async fn actual_form(
+    State(state): State<SharedState>,
+    Path((_, story_id)): Path<(String, i64)>,
+    BreakoutRoom(breakout): BreakoutRoom,
+    BreakoutUser(user): BreakoutUser,
+) -> Result<RecordActualTemplate, AppError> {
+    if !breakout.is_facilitator(&user) {
+        return Err(forbidden_actual());
+    }
+
+    let story = find_estimated(&state, &breakout, story_id).await?;
+
+    Ok(RecordActualTemplate {
+        breakout,
+        actual: story.actual_label().unwrap_or_default(),
+        story,
+        error: None,
+    })
+}
+
+async fn record_actual(
+    State(state): State<SharedState>,
+    Path((_, story_id)): Path<(String, i64)>,
+    BreakoutRoom(breakout): BreakoutRoom,
+    BreakoutUser(user): BreakoutUser,
+    Form(form): Form<ActualForm>,
+) -> Result<Response, AppError> {
+    if !breakout.is_facilitator(&user) {
+        return Err(forbidden_actual());
+    }
+
+    let story = find_estimated(&state, &breakout, story_id).await?;
+    let actual = match RecordActual::parse(story.id, &form.actual) {
+        Ok(actual) => actual,
+        Err(AppError::Validation(error)) => {
+            return Ok(RecordActualTemplate {
+                breakout,
+                story,
+                actual: form.actual,
+                error: Some(error),
+            }
+            .into_response());
+        }
+        Err(e) => return Err(e),
+    };
+
+    state
+        .story_service
+        .record_actual(breakout.id, &actual)
+        .await
+        .map_err(|e| AppError::internal(&format!("failed to record actual of {}", story.id), e))?;
+
+    let backlog = find_backlog(&state, &breakout).await?;
+    let mut channels = state.breakout_channels.lock().await;
+    BreakoutChannel::find_or_create(&mut channels, &state.room_events, &breakout.lookup_id)
+        .set_backlog(backlog);
+
+    let mut headers = HeaderMap::new();
+    headers.insert("HX-Trigger", "closeModal".parse().unwrap());
+
+    Ok((StatusCode::OK, headers).into_response())
+}
+
+/// The story, as long as it has an estimate to compare an actual with.
+async fn find_estimated(
+    state: &SharedState,
+    breakout: &Breakout,
+    story_id: i64,
+) -> Result<Story, AppError> {
+    let story = state
+        .story_service
+        .find(breakout.id, story_id)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => {
+                AppError::NotFound("This story doesn't exist anymore.".to_string())
+            }
+            e => AppError::from(e),
+        })?;
+
+    match story.is_estimated() {
+        true => Ok(story),
+        false => Err(AppError::Validation(
+            "Accept an estimate for this story first.".to_string(),
+        )),
+    }
+}
+
+async fn find_backlog(state: &SharedState, breakout: &Breakout) -> Result<Vec<Story>, AppError> {
+    state
+        .story_service
+        .find_by_breakout(breakout.id)
+        .await
+        .map_err(|e| AppError::internal(&format!("failed to load backlog of {}", breakout.id), e))
+}
+
+fn forbidden_backlog() -> AppError {
+    AppError::Forbidden("Only the facilitator can change the backlog.".to_string())
+}
+
+fn forbidden_estimate() -> AppError {
+    AppError::Forbidden("Only the facilitator can accept an estimate.".to_string())
+}
+
+fn forbidden_actual() -> AppError {
+    AppError::Forbidden("Only the facilitator can record actuals.".to_string())
+}
+
+fn nothing_to_estimate() -> AppError {
+    AppError::Validation("There's no story to estimate right now.".to_string())
+}
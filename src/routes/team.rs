@@ -0,0 +1,210 @@
+use askama::Template;
+use askama_web::WebTemplate;
+use axum::{
+    Form, Router,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, put},
+};
+use serde::Deserialize;
+
+use crate::{
+    SharedState,
+    domain::{
+        accuracy::Accuracy,
+        breakout::Breakout,
+        team::{NewTeam, Team, TeamHistory},
+    },
+    error::AppError,
+    extract::{breakout::BreakoutRoom, breakout_user::BreakoutUser},
+    routes::SharedContext,
+    util::chart::Chart,
+};
+
+pub fn routes() -> Router<SharedState> {
+    Router::new()
+        .route("/team/{team_id}", get(history))
+        .route("/breakout/{lookup_id}/team", get(team_form))
+        .route("/breakout/{lookup_id}/team", put(update_team))
+}
+
+#[derive(Template, WebTemplate)]
+#[template(path = "team.html")]
+struct TeamTemplate {
+    shared: SharedContext,
+    team: Team,
+    breakouts: Vec<Breakout>,
+    history: TeamHistory,
+    charts: Vec<Chart>,
+    accuracy: Accuracy,
+}
+
+#[derive(Template, WebTemplate)]
+#[template(path = "breakout_team.html")]
+struct BreakoutTeamTemplate {
+    breakout: Breakout,
+    /// The teams this breakout can join, including the one it's in.
+    teams: Vec<Team>,
+    current: Option<Team>,
+    new_team: String,
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TeamForm {
+    /// Empty to take the breakout out of its team.
+    team_id: String,
+    /// When filled in, starts a new team with this name instead.
+    new_team: String,
+}
+
+/// The team's trends across its latest sessions. Like a breakout, anyone
+/// with the link can look.
+async fn history(
+    State(state): State<SharedState>,
+    Path(lookup_id): Path<String>,
+) -> Result<TeamTemplate, AppError> {
+    let team = state
+        .team_service
+        .find_by_lookup_id(&lookup_id)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => AppError::NotFound("This team doesn't exist.".to_string()),
+            e => AppError::from(e),
+        })?;
+    let breakouts = state.team_service.breakouts(team.id).await?;
+    let history = state
+        .team_service
+        .history(team.id, &breakouts)
+        .await
+        .map_err(|e| {
+            AppError::internal(&format!("failed to load history of team {}", team.id), e)
+        })?;
+    let accuracy = state
+        .team_service
+        .accuracy(team.id, &breakouts)
+        .await
+        .map_err(|e| {
+            AppError::internal(&format!("failed to load accuracy of team {}", team.id), e)
+        })?;
+
+    Ok(TeamTemplate {
+        shared: SharedContext::new(&state.app_info, None).with_subtitle(&team.name),
+        charts: history.charts(),
+        team,
+        breakouts,
+        history,
+        accuracy,
+    })
+}
+
+async fn team_form(
+    State(state): State<SharedState>,
+    BreakoutRoom(breakout): BreakoutRoom,
+    BreakoutUser(user): BreakoutUser,
+) -> Result<BreakoutTeamTemplate, AppError> {
+    if !breakout.is_facilitator(&user) {
+        return Err(forbidden_team());
+    }
+
+    render(&state, breakout, user.id, String::new(), None).await
+}
+
+async fn update_team(
+    State(state): State<SharedState>,
+    BreakoutRoom(breakout): BreakoutRoom,
+    BreakoutUser(user): BreakoutUser,
+    Form(form): Form<TeamForm>,
+) -> Result<Response, AppError> {
+    if !breakout.is_facilitator(&user) {
+        return Err(forbidden_team());
+    }
+
+    let team_id = match form.new_team.trim().is_empty() {
+        false => match NewTeam::named(&form.new_team, Some(user.id)) {
+            Ok(team) => Some(state.team_service.create(&team).await?.id),
+            Err(AppError::Validation(error)) => {
+                return Ok(
+                    render(&state, breakout, user.id, form.new_team, Some(error))
+                        .await?
+                        .into_response(),
+                );
+            }
+            Err(e) => return Err(e),
+        },
+        true => match form.team_id.parse::<i64>().ok() {
+            Some(team_id) => {
+                let teams = available_teams(&state, &breakout, user.id).await?;
+                if !teams.iter().any(|t| t.id == team_id) {
+                    return Ok(render(
+                        &state,
+                        breakout,
+                        user.id,
+                        String::new(),
+                        Some("Please pick one of your teams.".to_string()),
+                    )
+                    .await?
+                    .into_response());
+                }
+                Some(team_id)
+            }
+            None => None,
+        },
+    };
+
+    state
+        .breakout_service
+        .update_team(breakout.id, team_id)
+        .await
+        .map_err(|e| AppError::internal(&format!("failed to update team of {}", breakout.id), e))?;
+
+    let mut headers = HeaderMap::new();
+    headers.insert("HX-Trigger", "closeModal".parse().unwrap());
+
+    Ok((StatusCode::OK, headers).into_response())
+}
+
+async fn render(
+    state: &SharedState,
+    breakout: Breakout,
+    user_id: i64,
+    new_team: String,
+    error: Option<String>,
+) -> Result<BreakoutTeamTemplate, AppError> {
+    let teams = available_teams(state, &breakout, user_id).await?;
+
+    Ok(BreakoutTeamTemplate {
+        current: teams
+            .iter()
+            .find(|t| Some(t.id) == breakout.team_id)
+            .cloned(),
+        breakout,
+        teams,
+        new_team,
+        error,
+    })
+}
+
+/// The user's teams, along with the breakout's own in case it was put
+/// there by someone else.
+async fn available_teams(
+    state: &SharedState,
+    breakout: &Breakout,
+    user_id: i64,
+) -> Result<Vec<Team>, AppError> {
+    let mut teams = state.team_service.find_for_user(user_id).await?;
+
+    if let Some(team_id) = breakout
+        .team_id
+        .filter(|id| !teams.iter().any(|t| t.id == *id))
+    {
+        teams.insert(0, state.team_service.find(team_id).await?);
+    }
+
+    Ok(teams)
+}
+
+fn forbidden_team() -> AppError {
+    AppError::Forbidden("Only the facilitator can change the breakout's team.".to_string())
+}
@@ -0,0 +1,267 @@
+use askama::Template;
+use askama_web::WebTemplate;
+use axum::{
+    Form, Router,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use serde::Deserialize;
+
+use crate::{
+    SharedState,
+    domain::{
+        breakout::Breakout,
+        breakout_channel::BreakoutChannel,
+        issue_tracker::{SaveTracker, TrackerKind},
+        story::RecordActual,
+    },
+    error::AppError,
+    extract::{breakout::BreakoutRoom, breakout_user::BreakoutUser},
+    jobs,
+};
+
+pub fn routes() -> Router<SharedState> {
+    Router::new()
+        .route("/breakout/{lookup_id}/tracker", get(tracker_form))
+        .route("/breakout/{lookup_id}/tracker", post(save_tracker))
+        .route("/breakout/{lookup_id}/tracker/import", post(import_issues))
+        .route("/breakout/{lookup_id}/tracker/actuals", post(pull_actuals))
+        .route(
+            "/breakout/{lookup_id}/stories/{story_id}/sync",
+            post(sync_story),
+        )
+}
+
+#[derive(Template, WebTemplate)]
+#[template(path = "breakout_tracker.html")]
+struct TrackerTemplate {
+    breakout: Breakout,
+    form: SaveTracker,
+    connected: bool,
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TrackerForm {
+    kind: TrackerKind,
+    base_url: String,
+    username: String,
+    secret: String,
+    query: String,
+    estimate_field: String,
+    actual_field: String,
+}
+
+async fn tracker_form(
+    State(state): State<SharedState>,
+    BreakoutRoom(breakout): BreakoutRoom,
+    BreakoutUser(user): BreakoutUser,
+) -> Result<TrackerTemplate, AppError> {
+    if !breakout.is_facilitator(&user) {
+        return Err(forbidden_tracker());
+    }
+
+    let connection = state.tracker_service.find_by_breakout(breakout.id).await?;
+
+    Ok(TrackerTemplate {
+        form: connection
+            .as_ref()
+            .map(SaveTracker::from)
+            .unwrap_or_else(|| SaveTracker {
+                estimate_field: "customfield_10016".to_string(),
+                ..SaveTracker::default()
+            }),
+        connected: connection.is_some(),
+        breakout,
+        error: None,
+    })
+}
+
+async fn save_tracker(
+    State(state): State<SharedState>,
+    BreakoutRoom(breakout): BreakoutRoom,
+    BreakoutUser(user): BreakoutUser,
+    Form(form): Form<TrackerForm>,
+) -> Result<Response, AppError> {
+    if !breakout.is_facilitator(&user) {
+        return Err(forbidden_tracker());
+    }
+
+    let tracker = SaveTracker {
+        breakout_id: breakout.id,
+        kind: form.kind,
+        base_url: form.base_url,
+        username: form.username,
+        secret: form.secret,
+        query: form.query,
+        estimate_field: form.estimate_field,
+        actual_field: form.actual_field,
+    };
+    let saved = match tracker.clone().validated() {
+        Ok(tracker) => state.tracker_service.save(&tracker).await,
+        Err(e) => Err(e),
+    };
+
+    match saved {
+        Ok(_) => Ok(TrackerTemplate {
+            form: SaveTracker {
+                secret: String::new(),
+                ..tracker
+            },
+            connected: true,
+            breakout,
+            error: None,
+        }
+        .into_response()),
+        Err(AppError::Validation(error)) => Ok(TrackerTemplate {
+            form: SaveTracker {
+                secret: String::new(),
+                ..tracker
+            },
+            connected: false,
+            breakout,
+            error: Some(error),
+        }
+        .into_response()),
+        Err(e) => Err(e),
+    }
+}
+
+async fn import_issues(
+    State(state): State<SharedState>,
+    BreakoutRoom(breakout): BreakoutRoom,
+    BreakoutUser(user): BreakoutUser,
+) -> Result<Response, AppError> {
+    if !breakout.is_facilitator(&user) {
+        return Err(forbidden_tracker());
+    }
+
+    let connection = state
+        .tracker_service
+        .find_by_breakout(breakout.id)
+        .await?
+        .ok_or_else(|| AppError::Validation("Connect an issue tracker first.".to_string()))?;
+
+    let issues = state.tracker_service.fetch_issues(&connection).await?;
+    state
+        .story_service
+        .import(breakout.id, &issues)
+        .await
+        .map_err(|e| AppError::internal(&format!("failed to import into {}", breakout.id), e))?;
+
+    let backlog = state
+        .story_service
+        .find_by_breakout(breakout.id)
+        .await
+        .map_err(|e| {
+            AppError::internal(&format!("failed to load backlog of {}", breakout.id), e)
+        })?;
+    let mut channels = state.breakout_channels.lock().await;
+    BreakoutChannel::find_or_create(&mut channels, &state.room_events, &breakout.lookup_id)
+        .set_backlog(backlog);
+
+    let mut headers = HeaderMap::new();
+    headers.insert("HX-Trigger", "closeModal".parse().unwrap());
+
+    Ok((StatusCode::OK, headers).into_response())
+}
+
+/// Reads the actual effort of every estimated story that came from the
+/// tracker, keeping what's already recorded when the tracker has nothing.
+async fn pull_actuals(
+    State(state): State<SharedState>,
+    BreakoutRoom(breakout): BreakoutRoom,
+    BreakoutUser(user): BreakoutUser,
+) -> Result<Response, AppError> {
+    if !breakout.is_facilitator(&user) {
+        return Err(forbidden_tracker());
+    }
+
+    let connection = state
+        .tracker_service
+        .find_by_breakout(breakout.id)
+        .await?
+        .filter(|connection| !connection.actual_field.is_empty())
+        .ok_or_else(|| {
+            AppError::Validation("Set where actuals are read from first.".to_string())
+        })?;
+
+    let stories = state.story_service.find_by_breakout(breakout.id).await?;
+    let mut failures = vec![];
+    for story in stories.iter().filter(|s| s.is_estimated()) {
+        let Some(key) = &story.external_key else {
+            continue;
+        };
+
+        match state.tracker_service.fetch_actual(&connection, key).await {
+            Ok(Some(actual)) => {
+                let actual = RecordActual {
+                    story_id: story.id,
+                    actual: Some(actual),
+                };
+                state
+                    .story_service
+                    .record_actual(breakout.id, &actual)
+                    .await
+                    .map_err(|e| {
+                        AppError::internal(&format!("failed to record actual of {}", story.id), e)
+                    })?;
+            }
+            Ok(None) => {}
+            Err(e) => failures.push(e),
+        }
+    }
+
+    let backlog = state
+        .story_service
+        .find_by_breakout(breakout.id)
+        .await
+        .map_err(|e| {
+            AppError::internal(&format!("failed to load backlog of {}", breakout.id), e)
+        })?;
+    {
+        let mut channels = state.breakout_channels.lock().await;
+        BreakoutChannel::find_or_create(&mut channels, &state.room_events, &breakout.lookup_id)
+            .set_backlog(backlog);
+    }
+
+    if let Some(first) = failures.first() {
+        return Err(AppError::Validation(format!(
+            "Couldn't read the actuals of {} {}. {first}",
+            failures.len(),
+            match failures.len() {
+                1 => "issue",
+                _ => "issues",
+            }
+        )));
+    }
+
+    let mut headers = HeaderMap::new();
+    headers.insert("HX-Trigger", "closeModal".parse().unwrap());
+
+    Ok((StatusCode::OK, headers).into_response())
+}
+
+/// Tries writing a story's estimate back to the tracker again, e.g. after
+/// the earlier attempts failed.
+async fn sync_story(
+    State(state): State<SharedState>,
+    Path((_, story_id)): Path<(String, i64)>,
+    BreakoutRoom(breakout): BreakoutRoom,
+    BreakoutUser(user): BreakoutUser,
+) -> Result<StatusCode, AppError> {
+    if !breakout.is_facilitator(&user) {
+        return Err(forbidden_tracker());
+    }
+
+    let story = state.story_service.find(breakout.id, story_id).await?;
+    tokio::spawn(jobs::tracker_sync::run(state.clone(), breakout, story));
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn forbidden_tracker() -> AppError {
+    AppError::Forbidden("Only the facilitator can manage the issue tracker.".to_string())
+}
@@ -0,0 +1,137 @@
+use askama::Template;
+use askama_web::WebTemplate;
+use axum::{
+    Form, Router,
+    extract::{Path, State},
+    routing::{delete, get, post},
+};
+use serde::Deserialize;
+
+use crate::{
+    SharedState,
+    domain::{
+        breakout::Breakout,
+        webhook::{DeliveryLogEntry, NewWebhook, Webhook},
+    },
+    error::AppError,
+    extract::{breakout::BreakoutRoom, breakout_user::BreakoutUser},
+};
+
+pub fn routes() -> Router<SharedState> {
+    Router::new()
+        .route("/breakout/{lookup_id}/webhooks", get(webhooks))
+        .route("/breakout/{lookup_id}/webhooks", post(create_webhook))
+        .route(
+            "/breakout/{lookup_id}/webhooks/{webhook_id}",
+            delete(delete_webhook),
+        )
+}
+
+#[derive(Template, WebTemplate)]
+#[template(path = "breakout_webhooks.html")]
+struct WebhooksTemplate {
+    breakout: Breakout,
+    webhooks: Vec<Webhook>,
+    deliveries: Vec<DeliveryLogEntry>,
+    form: NewWebhook,
+    /// The signing secret of a webhook that was just created.
+    secret: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct WebhookForm {
+    url: String,
+    on_round_revealed: Option<String>,
+    on_estimate_accepted: Option<String>,
+    on_session_ended: Option<String>,
+}
+
+async fn webhooks(
+    State(state): State<SharedState>,
+    BreakoutRoom(breakout): BreakoutRoom,
+    BreakoutUser(user): BreakoutUser,
+) -> Result<WebhooksTemplate, AppError> {
+    if !breakout.is_facilitator(&user) {
+        return Err(forbidden_webhooks());
+    }
+
+    render(&state, breakout, NewWebhook::default(), None, None).await
+}
+
+async fn create_webhook(
+    State(state): State<SharedState>,
+    BreakoutRoom(breakout): BreakoutRoom,
+    BreakoutUser(user): BreakoutUser,
+    Form(form): Form<WebhookForm>,
+) -> Result<WebhooksTemplate, AppError> {
+    if !breakout.is_facilitator(&user) {
+        return Err(forbidden_webhooks());
+    }
+
+    let webhook = NewWebhook {
+        breakout_id: breakout.id,
+        url: form.url,
+        on_round_revealed: form.on_round_revealed.is_some(),
+        on_estimate_accepted: form.on_estimate_accepted.is_some(),
+        on_session_ended: form.on_session_ended.is_some(),
+    };
+    let created = match webhook.clone().validated() {
+        Ok(webhook) => state.webhook_service.create(&webhook).await,
+        Err(e) => Err(e),
+    };
+
+    match created {
+        Ok((_, secret)) => {
+            render(&state, breakout, NewWebhook::default(), Some(secret), None).await
+        }
+        Err(AppError::Validation(error)) => {
+            render(&state, breakout, webhook, None, Some(error)).await
+        }
+        Err(e) => Err(e),
+    }
+}
+
+async fn delete_webhook(
+    State(state): State<SharedState>,
+    Path((_, webhook_id)): Path<(String, i64)>,
+    BreakoutRoom(breakout): BreakoutRoom,
+    BreakoutUser(user): BreakoutUser,
+) -> Result<WebhooksTemplate, AppError> {
+    if !breakout.is_facilitator(&user) {
+        return Err(forbidden_webhooks());
+    }
+
+    if !state
+        .webhook_service
+        .delete(breakout.id, webhook_id)
+        .await?
+    {
+        return Err(AppError::NotFound(
+            "This webhook doesn't exist anymore.".to_string(),
+        ));
+    }
+
+    render(&state, breakout, NewWebhook::default(), None, None).await
+}
+
+async fn render(
+    state: &SharedState,
+    breakout: Breakout,
+    form: NewWebhook,
+    secret: Option<String>,
+    error: Option<String>,
+) -> Result<WebhooksTemplate, AppError> {
+    Ok(WebhooksTemplate {
+        webhooks: state.webhook_service.find_by_breakout(breakout.id).await?,
+        deliveries: state.webhook_service.recent_deliveries(breakout.id).await?,
+        breakout,
+        form,
+        secret,
+        error,
+    })
+}
+
+fn forbidden_webhooks() -> AppError {
+    AppError::Forbidden("Only the facilitator can manage webhooks.".to_string())
+}
@@ -0,0 +1,369 @@
+/// The size of every chart, in SVG units. Charts scale to fit their
+/// container, so this mostly sets the proportions and the text size.
+const WIDTH: f64 = 600.0;
+const HEIGHT: f64 = 200.0;
+/// Room around the plot for the axis labels.
+const LEFT: f64 = 40.0;
+const RIGHT: f64 = 8.0;
+const TOP: f64 = 12.0;
+const BOTTOM: f64 = 28.0;
+/// Beyond this many labels along the bottom, only every few are shown.
+const MAX_LABELS: usize = 12;
+
+/// A chart laid out on the server, ready for `_partials/chart.html` to
+/// draw as an SVG.
+pub struct Chart {
+    pub title: String,
+    pub description: String,
+    pub width: f64,
+    pub height: f64,
+    /// Where the plot starts and ends horizontally, for the grid lines.
+    pub left: f64,
+    pub right: f64,
+    pub grid: Vec<GridLine>,
+    pub bars: Vec<Bar>,
+    /// The points of a line chart, in the form of a polyline's `points`.
+    pub line: Option<String>,
+    /// A dashed line to compare against, in the same form as `line`.
+    pub reference: Option<String>,
+    pub points: Vec<Point>,
+    pub labels: Vec<Label>,
+    entries: usize,
+}
+impl Chart {
+    /// One bar per entry.
+    pub fn bars(title: &str, description: &str, data: &[(String, f64)], suffix: &str) -> Self {
+        let max = nice_max(data.iter().map(|(_, value)| *value).fold(0.0, f64::max));
+        let mut chart = Self::new(title, description, max, suffix, data.len());
+
+        let slot = chart.slot_width();
+        chart.bars = data
+            .iter()
+            .enumerate()
+            .map(|(i, (label, value))| {
+                let y = chart.y(*value, max);
+                Bar {
+                    x: round(LEFT + slot * i as f64 + slot * 0.15),
+                    y,
+                    width: round(slot * 0.7),
+                    height: round(HEIGHT - BOTTOM - y),
+                    title: format!("{label}: {}{suffix}", format_value(*value)),
+                }
+            })
+            .collect();
+        chart.labels = chart.labels_for(data.iter().map(|(label, _)| label));
+
+        chart
+    }
+
+    /// A line through every entry that has a value, against a fixed
+    /// maximum such as 100 for percentages.
+    pub fn line(
+        title: &str,
+        description: &str,
+        data: &[(String, Option<f64>)],
+        max: Option<f64>,
+        suffix: &str,
+    ) -> Self {
+        let max = max.unwrap_or_else(|| {
+            nice_max(
+                data.iter()
+                    .filter_map(|(_, value)| *value)
+                    .fold(0.0, f64::max),
+            )
+        });
+        let mut chart = Self::new(title, description, max, suffix, data.len());
+
+        let slot = chart.slot_width();
+        chart.points = data
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (label, value))| {
+                value.map(|value| Point {
+                    x: round(LEFT + slot * i as f64 + slot / 2.0),
+                    y: chart.y(value, max),
+                    title: format!("{label}: {}{suffix}", format_value(value)),
+                })
+            })
+            .collect();
+        if chart.points.len() > 1 {
+            chart.line = Some(
+                chart
+                    .points
+                    .iter()
+                    .map(|p| format!("{},{}", p.x, p.y))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            );
+        }
+        chart.labels = chart.labels_for(data.iter().map(|(label, _)| label));
+
+        chart
+    }
+
+    /// One point per `(hover text, x, y)` entry, with both axes on the
+    /// same scale and a dashed diagonal where x and y are equal.
+    pub fn scatter(
+        title: &str,
+        description: &str,
+        data: &[(String, f64, f64)],
+        suffix: &str,
+    ) -> Self {
+        let max = nice_max(data.iter().map(|(_, x, y)| x.max(*y)).fold(0.0, f64::max));
+        let mut chart = Self::new(title, description, max, suffix, data.len());
+
+        chart.points = data
+            .iter()
+            .map(|(title, x, y)| Point {
+                x: chart.x(*x, max),
+                y: chart.y(*y, max),
+                title: title.clone(),
+            })
+            .collect();
+        if !data.is_empty() {
+            chart.reference = Some(format!(
+                "{},{} {},{}",
+                chart.x(0.0, max),
+                chart.y(0.0, max),
+                chart.x(max, max),
+                chart.y(max, max)
+            ));
+            chart.labels = [0.0, max / 2.0, max]
+                .into_iter()
+                .map(|value| Label {
+                    x: chart.x(value, max),
+                    y: round(HEIGHT - BOTTOM / 3.0),
+                    text: format!("{}{suffix}", format_value(value)),
+                })
+                .collect();
+        }
+
+        chart
+    }
+
+    fn new(title: &str, description: &str, max: f64, suffix: &str, entries: usize) -> Self {
+        let mut chart = Self {
+            title: title.to_string(),
+            description: description.to_string(),
+            width: WIDTH,
+            height: HEIGHT,
+            left: LEFT,
+            right: WIDTH - RIGHT,
+            grid: vec![],
+            bars: vec![],
+            line: None,
+            reference: None,
+            points: vec![],
+            labels: vec![],
+            entries,
+        };
+        if entries > 0 {
+            chart.grid = [0.0, max / 2.0, max]
+                .into_iter()
+                .map(|value| GridLine {
+                    y: chart.y(value, max),
+                    label: format!("{}{suffix}", format_value(value)),
+                })
+                .collect();
+        }
+
+        chart
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bars.is_empty() && self.points.is_empty()
+    }
+
+    /// How much room each entry gets along the bottom.
+    fn slot_width(&self) -> f64 {
+        (WIDTH - LEFT - RIGHT) / self.entries.max(1) as f64
+    }
+
+    fn x(&self, value: f64, max: f64) -> f64 {
+        round(LEFT + (WIDTH - LEFT - RIGHT) * (value / max).clamp(0.0, 1.0))
+    }
+
+    fn y(&self, value: f64, max: f64) -> f64 {
+        let plot = HEIGHT - TOP - BOTTOM;
+        round(HEIGHT - BOTTOM - plot * (value / max).clamp(0.0, 1.0))
+    }
+
+    /// Labels along the bottom, skipping some when there are too many to
+    /// fit.
+    fn labels_for<'a>(&self, labels: impl Iterator<Item = &'a String>) -> Vec<Label> {
+        let every = self.entries.div_ceil(MAX_LABELS).max(1);
+        let slot = self.slot_width();
+
+        labels
+            .enumerate()
+            .filter(|(i, _)| i % every == 0)
+            .map(|(i, text)| Label {
+                x: round(LEFT + slot * i as f64 + slot / 2.0),
+                y: round(HEIGHT - BOTTOM / 3.0),
+                text: text.clone(),
+            })
+            .collect()
+    }
+}
+
+pub struct GridLine {
+    pub y: f64,
+    pub label: String,
+}
+
+pub struct Bar {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    /// Shown on hover.
+    pub title: String,
+}
+
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+    /// Shown on hover.
+    pub title: String,
+}
+
+pub struct Label {
+    pub x: f64,
+    pub y: f64,
+    pub text: String,
+}
+
+/// The top of the scale: the smallest of 1, 2 or 5 times a power of ten
+/// that fits the largest value.
+fn nice_max(value: f64) -> f64 {
+    if value <= 0.0 {
+        return 1.0;
+    }
+
+    let magnitude = 10f64.powf(value.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|step| step * magnitude)
+        .find(|max| *max >= value)
+        .unwrap_or(10.0 * magnitude)
+}
+
+fn round(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+/// A value rounded to one decimal, without a trailing ".0".
+fn format_value(value: f64) -> String {
+    let rounded = round(value);
+    match rounded.fract() == 0.0 {
+        true => format!("{rounded:.0}"),
+        false => format!("{rounded:.1}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(values: &[f64]) -> Vec<(String, f64)> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (format!("#{i}"), *value))
+            .collect()
+    }
+
+    #[test]
+    fn scales_to_a_round_maximum() {
+        assert_eq!(nice_max(0.0), 1.0);
+        assert_eq!(nice_max(0.3), 0.5);
+        assert_eq!(nice_max(7.0), 10.0);
+        assert_eq!(nice_max(12.0), 20.0);
+        assert_eq!(nice_max(100.0), 100.0);
+    }
+
+    #[test]
+    fn an_empty_chart_draws_nothing() {
+        let bars = Chart::bars("Points", "", &[], "");
+        let line = Chart::line("Consensus", "", &[], Some(100.0), "%");
+
+        for chart in [bars, line] {
+            assert!(chart.is_empty());
+            assert!(chart.grid.is_empty());
+            assert!(chart.labels.is_empty());
+            assert_eq!(chart.line, None);
+        }
+    }
+
+    #[test]
+    fn a_single_bar_fills_the_plot_width() {
+        let chart = Chart::bars("Points", "", &entries(&[3.0]), " pts");
+        let bar = &chart.bars[0];
+
+        let grid: Vec<_> = chart.grid.iter().map(|g| (g.y, g.label.as_str())).collect();
+        assert_eq!(grid, [(172.0, "0 pts"), (92.0, "2.5 pts"), (12.0, "5 pts")]);
+        assert_eq!((bar.x, bar.width), (122.8, 386.4));
+        assert_eq!((bar.y, bar.height), (76.0, 96.0));
+        assert_eq!(bar.title, "#0: 3 pts");
+    }
+
+    #[test]
+    fn zero_bars_sit_on_the_axis() {
+        let chart = Chart::bars("Points", "", &entries(&[0.0, 0.0]), "");
+
+        assert!(chart.bars.iter().all(|bar| bar.height == 0.0));
+        assert_eq!(chart.grid.last().unwrap().label, "1");
+    }
+
+    #[test]
+    fn a_single_point_has_no_line() {
+        let data = [("Oct 1".to_string(), Some(50.0))];
+        let chart = Chart::line("Consensus", "", &data, Some(100.0), "%");
+
+        assert_eq!(chart.points.len(), 1);
+        assert_eq!((chart.points[0].x, chart.points[0].y), (316.0, 92.0));
+        assert_eq!(chart.line, None);
+    }
+
+    #[test]
+    fn lines_skip_entries_without_a_value() {
+        let data = [
+            ("a".to_string(), Some(0.0)),
+            ("b".to_string(), None),
+            ("c".to_string(), Some(150.0)),
+        ];
+        let chart = Chart::line("Consensus", "", &data, Some(100.0), "%");
+
+        assert_eq!(chart.points.len(), 2);
+        assert_eq!(chart.line.as_deref(), Some("132,172 500,12"));
+        assert_eq!(chart.labels.len(), 3);
+    }
+
+    #[test]
+    fn thins_out_crowded_labels() {
+        let chart = Chart::bars("Points", "", &entries(&[1.0; 30]), "");
+
+        assert_eq!(chart.bars.len(), 30);
+        assert_eq!(chart.labels.len(), 10);
+        assert_eq!(chart.labels[1].text, "#3");
+    }
+
+    #[test]
+    fn scatters_on_a_shared_scale() {
+        let chart = Chart::scatter(
+            "Estimate vs Actual",
+            "",
+            &[("Sign in".to_string(), 5.0, 8.0)],
+            "",
+        );
+
+        assert_eq!((chart.points[0].x, chart.points[0].y), (316.0, 44.0));
+        assert_eq!(chart.reference.as_deref(), Some("40,172 592,12"));
+        let labels: Vec<_> = chart.labels.iter().map(|l| l.text.as_str()).collect();
+        assert_eq!(labels, ["0", "5", "10"]);
+
+        let empty = Chart::scatter("Estimate vs Actual", "", &[], "");
+        assert!(empty.is_empty());
+        assert_eq!(empty.reference, None);
+    }
+}
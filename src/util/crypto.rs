@@ -0,0 +1,108 @@
+use aes_gcm::{
+    Aes256Gcm, Key, Nonce,
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use hmac::{Hmac, Mac};
+use log::warn;
+use sha2::{Digest, Sha256};
+use std::env;
+
+use crate::error::AppError;
+
+/// Encrypts credentials for third-party services before they are stored,
+/// using a key derived from `CREDENTIALS_SECRET`.
+pub struct CredentialCipher {
+    cipher: Aes256Gcm,
+}
+impl CredentialCipher {
+    pub fn new() -> Self {
+        let key: [u8; 32] = match env::var("CREDENTIALS_SECRET") {
+            Ok(secret) => {
+                assert!(
+                    secret.len() >= 32,
+                    "CREDENTIALS_SECRET must be at least 32 bytes"
+                );
+                Sha256::digest(secret.as_bytes()).into()
+            }
+            Err(_) => {
+                warn!(
+                    "CREDENTIALS_SECRET not defined, stored credentials will not survive a restart."
+                );
+                Aes256Gcm::generate_key(OsRng).into()
+            }
+        };
+
+        Self {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)),
+        }
+    }
+
+    /// Encrypts the secret with a fresh nonce, returning both as base64.
+    pub fn encrypt(&self, secret: &str) -> Result<String, AppError> {
+        let nonce = Aes256Gcm::generate_nonce(OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, secret.as_bytes())
+            .map_err(|e| AppError::internal("failed to encrypt credentials", e))?;
+
+        Ok(STANDARD.encode([nonce.as_slice(), &ciphertext].concat()))
+    }
+
+    pub fn decrypt(&self, encrypted: &str) -> Result<String, AppError> {
+        let bytes = STANDARD
+            .decode(encrypted)
+            .map_err(|e| AppError::internal("failed to decode credentials", e))?;
+        if bytes.len() < 12 {
+            return Err(AppError::Internal(
+                "stored credentials are too short".to_string(),
+            ));
+        }
+
+        let (nonce, ciphertext) = bytes.split_at(12);
+        let secret = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|e| AppError::internal("failed to decrypt credentials", e))?;
+
+        String::from_utf8(secret).map_err(|e| AppError::internal("credentials aren't UTF-8", e))
+    }
+}
+impl Default for CredentialCipher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Signs the message with HMAC-SHA256, returning the signature as hex.
+pub fn sign(secret: &str, message: &str) -> String {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any size");
+    mac.update(message.as_bytes());
+
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Checks a hex HMAC-SHA256 signature of the message, in constant time.
+pub fn verify(secret: &str, message: &str, signature: &str) -> bool {
+    let bytes: Option<Vec<u8>> = (0..signature.len())
+        .step_by(2)
+        .map(|i| {
+            signature
+                .get(i..i + 2)
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+        })
+        .collect();
+    let Some(bytes) = bytes else {
+        return false;
+    };
+
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any size");
+    mac.update(message.as_bytes());
+    mac.verify_slice(&bytes).is_ok()
+}
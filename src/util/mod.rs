@@ -1 +1,6 @@
+pub mod chart;
+pub mod crypto;
 pub mod htmx;
+pub mod text;
+pub mod time;
+pub mod url;
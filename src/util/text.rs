@@ -0,0 +1,36 @@
+use unicode_normalization::UnicodeNormalization;
+use unicode_properties::{GeneralCategory, UnicodeGeneralCategory};
+
+/// NFC-normalizes the input and collapses every run of whitespace,
+/// including line breaks, into a single space.
+pub fn normalize_line(input: &str) -> String {
+    let normalized: String = input.nfc().collect();
+    normalized.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Like `normalize_line`, but keeps line breaks so that the text can span
+/// multiple paragraphs.
+pub fn normalize_text(input: &str) -> String {
+    let normalized: String = input.nfc().collect();
+    normalized
+        .lines()
+        .map(normalize_line)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// Whether the text contains control characters other than line breaks.
+pub fn has_control_chars(input: &str) -> bool {
+    input.chars().any(|c| c.is_control() && c != '\n')
+}
+
+/// Whether the text contains invisible formatting characters, like zero
+/// width spaces or right-to-left overrides, which can make one name look
+/// like another.
+pub fn has_format_chars(input: &str) -> bool {
+    input
+        .chars()
+        .any(|c| c.general_category() == GeneralCategory::Format)
+}
@@ -0,0 +1,36 @@
+use chrono::{NaiveDateTime, Utc};
+
+/// Describes how long ago a UTC timestamp was, e.g. "5 minutes ago".
+pub fn time_ago(timestamp: &NaiveDateTime) -> String {
+    let elapsed = Utc::now().naive_utc() - *timestamp;
+
+    let (amount, unit) = if elapsed.num_minutes() < 1 {
+        return "just now".to_string();
+    } else if elapsed.num_hours() < 1 {
+        (elapsed.num_minutes(), "minute")
+    } else if elapsed.num_days() < 1 {
+        (elapsed.num_hours(), "hour")
+    } else if elapsed.num_weeks() < 1 {
+        (elapsed.num_days(), "day")
+    } else {
+        return timestamp.format("%b %-d, %Y").to_string();
+    };
+
+    match amount {
+        1 => format!("1 {unit} ago"),
+        n => format!("{n} {unit}s ago"),
+    }
+}
+
+/// Describes a length of time in its two largest units, e.g. "1h 5m" or
+/// "3m 20s".
+pub fn duration_label(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    let (hours, minutes, seconds) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+
+    match (hours, minutes) {
+        (0, 0) => format!("{seconds}s"),
+        (0, minutes) => format!("{minutes}m {seconds}s"),
+        (hours, minutes) => format!("{hours}h {minutes}m"),
+    }
+}
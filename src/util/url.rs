@@ -0,0 +1,93 @@
+use reqwest::{
+    Client, Url,
+    dns::{Addrs, Name, Resolve, Resolving},
+    redirect,
+};
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
+
+/// Parses a URL we're about to send requests and credentials to on a
+/// user's behalf. It has to be https, so nothing goes out in the clear,
+/// and point somewhere on the internet rather than at this server or the
+/// network it runs in.
+pub fn parse_public_url(value: &str) -> Result<Url, &'static str> {
+    let url = Url::parse(value).map_err(|_| "Please enter a URL starting with https://.")?;
+    if url.scheme() != "https" {
+        return Err("Please enter a URL starting with https://.");
+    }
+
+    let host = url
+        .host_str()
+        .ok_or("Please enter a URL starting with https://.")?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .trim_end_matches('.')
+        .to_ascii_lowercase();
+    let is_local = match host.parse::<IpAddr>() {
+        Ok(ip) => is_local_ip(ip),
+        Err(_) => host == "localhost" || host.ends_with(".localhost"),
+    };
+
+    match is_local {
+        true => Err("Please enter a public address, not a local or private one."),
+        false => Ok(url),
+    }
+}
+
+/// An HTTP client for the URLs users give us. It doesn't follow redirects,
+/// and only connects to the public addresses a host resolves to, so a URL
+/// that passed `parse_public_url` can't be pointed back at this server or
+/// its network later on.
+pub fn public_client(timeout: Duration) -> Client {
+    Client::builder()
+        .timeout(timeout)
+        .redirect(redirect::Policy::none())
+        .dns_resolver(Arc::new(PublicResolver))
+        .build()
+        .expect("Failed to build HTTP client")
+}
+
+/// Looks hosts up like the system does, leaving out local and private
+/// addresses.
+struct PublicResolver;
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|addr| !is_local_ip(addr.ip()))
+                .collect();
+
+            match addrs.is_empty() {
+                true => Err(format!("{} has no public address", name.as_str()).into()),
+                false => Ok(Box::new(addrs.into_iter()) as Addrs),
+            }
+        })
+    }
+}
+
+fn is_local_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                // 100.64.0.0/10, shared by carrier-grade NAT.
+                || (ip.octets()[0] == 100 && ip.octets()[1] & 0xc0 == 64)
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_local_ip(IpAddr::V4(ip)),
+            None => {
+                ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local()
+            }
+        },
+    }
+}
@@ -0,0 +1,12 @@
+use axum::Router;
+use tokio::net::TcpListener;
+
+/// Serves the router on a free local port, standing in for a service we
+/// talk to, and gives its base URL.
+pub async fn serve(router: Router) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+    format!("http://{address}")
+}
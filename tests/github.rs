@@ -0,0 +1,341 @@
+mod common;
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    routing::{delete, get, post},
+};
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use guess_rs::{
+    domain::issue_tracker::IssueTracker, infrastructure::trackers::github::GitHubClient,
+};
+
+/// What the stand-in GitHub was asked to do.
+#[derive(Default)]
+struct GitHub {
+    searches: Vec<HashMap<String, String>>,
+    labels: Vec<String>,
+    removed: Vec<String>,
+    added: Vec<String>,
+    mutations: Vec<Value>,
+}
+
+type Shared = Arc<Mutex<GitHub>>;
+
+async fn github(labels: &[&str]) -> (String, Shared) {
+    let shared = Arc::new(Mutex::new(GitHub {
+        labels: labels.iter().map(|l| l.to_string()).collect(),
+        ..Default::default()
+    }));
+    let router = Router::new()
+        .route("/search/issues", get(search))
+        .route(
+            "/repos/acme/api/issues/42/labels",
+            get(list_labels).post(add_labels),
+        )
+        .route(
+            "/repos/acme/api/issues/42/labels/{name}",
+            delete(remove_label),
+        )
+        .route("/graphql", post(graphql))
+        .with_state(shared.clone());
+
+    (common::serve(router).await, shared)
+}
+
+fn client(base_url: &str, estimate_field: &str, actual_field: &str) -> GitHubClient {
+    GitHubClient {
+        http: Client::new(),
+        base_url: base_url.to_string(),
+        token: "ghp_secret".to_string(),
+        query: "repo:acme/api label:ready".to_string(),
+        estimate_field: estimate_field.to_string(),
+        actual_field: actual_field.to_string(),
+    }
+}
+
+fn is_signed_in(headers: &HeaderMap) -> bool {
+    headers
+        .get("authorization")
+        .is_some_and(|h| h == "Bearer ghp_secret")
+}
+
+/// A full first page of 100 issues and a second one with 3 more.
+async fn search(
+    State(github): State<Shared>,
+    headers: HeaderMap,
+    Query(query): Query<HashMap<String, String>>,
+) -> Result<Json<Value>, StatusCode> {
+    if !is_signed_in(&headers) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    let (first, count) = match query.get("page").map(String::as_str) {
+        Some("1") => (1, 100),
+        Some("2") => (101, 3),
+        _ => (0, 0),
+    };
+    let items: Vec<Value> = (first..first + count)
+        .map(|number| {
+            json!({
+                "number": number,
+                "title": format!("Issue {number}"),
+                "html_url": format!("https://github.com/acme/api/issues/{number}"),
+                "repository_url": "https://api.github.com/repos/acme/api",
+            })
+        })
+        .collect();
+    github.lock().unwrap().searches.push(query);
+
+    Ok(Json(json!({ "total_count": 103, "items": items })))
+}
+
+async fn list_labels(State(github): State<Shared>) -> Json<Value> {
+    let labels = &github.lock().unwrap().labels;
+    Json(labels.iter().map(|name| json!({ "name": name })).collect())
+}
+
+async fn add_labels(State(github): State<Shared>, Json(body): Json<Value>) -> StatusCode {
+    let mut github = github.lock().unwrap();
+    for label in body["labels"].as_array().unwrap() {
+        let label = label.as_str().unwrap().to_string();
+        github.labels.push(label.clone());
+        github.added.push(label);
+    }
+    StatusCode::OK
+}
+
+async fn remove_label(State(github): State<Shared>, Path(name): Path<String>) -> StatusCode {
+    let mut github = github.lock().unwrap();
+    match github.labels.iter().position(|l| *l == name) {
+        Some(index) => {
+            github.labels.remove(index);
+            github.removed.push(name);
+            StatusCode::OK
+        }
+        None => StatusCode::NOT_FOUND,
+    }
+}
+
+/// Issue 42 is in projects 2 and 3. Project 3 has number fields called
+/// "Estimate" and "Actual", the latter set to 6.
+async fn graphql(State(github): State<Shared>, Json(body): Json<Value>) -> Json<Value> {
+    let query = body["query"].as_str().unwrap_or_default();
+    let variables = &body["variables"];
+
+    if query.contains("mutation") {
+        github.lock().unwrap().mutations.push(variables.clone());
+        return Json(json!({
+            "data": { "updateProjectV2ItemFieldValue": { "projectV2Item": { "id": "ITEM_3" } } }
+        }));
+    }
+
+    let field = variables["field"].as_str().unwrap_or_default();
+    if !["Estimate", "Actual"].contains(&field) {
+        return Json(json!({
+            "data": null,
+            "errors": [{ "message": format!("No field called {field}") }],
+        }));
+    }
+
+    let nodes = match query.contains("fieldValueByName") {
+        true => json!([
+            { "project": { "number": 2 }, "fieldValueByName": null },
+            {
+                "project": { "number": 3 },
+                "fieldValueByName": match field {
+                    "Actual" => json!({ "number": 6.0 }),
+                    _ => Value::Null,
+                },
+            },
+        ]),
+        false => json!([
+            { "id": "ITEM_2", "project": { "id": "PROJECT_2", "number": 2, "field": {} } },
+            {
+                "id": "ITEM_3",
+                "project": { "id": "PROJECT_3", "number": 3, "field": { "id": format!("FIELD_{field}") } },
+            },
+        ]),
+    };
+
+    Json(json!({
+        "data": { "repository": { "issue": { "projectItems": { "nodes": nodes } } } }
+    }))
+}
+
+#[tokio::test]
+async fn imports_every_page_of_search_results() {
+    let (base_url, github) = github(&[]).await;
+
+    let issues = client(&base_url, "estimate: {}", "")
+        .fetch_issues()
+        .await
+        .unwrap();
+
+    assert_eq!(issues.len(), 103);
+    assert_eq!(issues[0].key, "acme/api#1");
+    assert_eq!(issues[102].key, "acme/api#103");
+    assert_eq!(issues[102].title, "Issue 103");
+    assert_eq!(issues[102].url, "https://github.com/acme/api/issues/103");
+
+    let github = github.lock().unwrap();
+    assert_eq!(github.searches.len(), 2);
+    assert_eq!(
+        github.searches[0]["q"],
+        "repo:acme/api label:ready is:issue is:open"
+    );
+    assert_eq!(github.searches[1]["page"], "2");
+}
+
+#[tokio::test]
+async fn import_fails_when_the_token_is_rejected() {
+    let (base_url, _) = github(&[]).await;
+    let mut client = client(&base_url, "estimate: {}", "");
+    client.token = "wrong".to_string();
+
+    let error = client.fetch_issues().await.unwrap_err();
+
+    assert!(error.0.contains("401"), "{error}");
+}
+
+#[tokio::test]
+async fn swaps_estimate_labels() {
+    let (base_url, github) = github(&["bug", "estimate: 3", "estimate: 5", "ready"]).await;
+
+    client(&base_url, "estimate: {}", "")
+        .write_estimate("acme/api#42", "8")
+        .await
+        .unwrap();
+
+    let github = github.lock().unwrap();
+    assert_eq!(github.removed, ["estimate: 3", "estimate: 5"]);
+    assert_eq!(github.added, ["estimate: 8"]);
+    assert_eq!(github.labels, ["bug", "ready", "estimate: 8"]);
+}
+
+#[tokio::test]
+async fn keeps_a_label_that_already_has_the_estimate() {
+    let (base_url, github) = github(&["bug", "8 points"]).await;
+
+    client(&base_url, "{} points", "")
+        .write_estimate("acme/api#42", "8")
+        .await
+        .unwrap();
+
+    let github = github.lock().unwrap();
+    assert!(github.removed.is_empty());
+    assert_eq!(github.labels, ["bug", "8 points", "8 points"]);
+}
+
+#[tokio::test]
+async fn refuses_a_label_template_that_matches_every_label() {
+    let (base_url, github) = github(&["bug", "ready"]).await;
+
+    let error = client(&base_url, "{}", "")
+        .write_estimate("acme/api#42", "8")
+        .await
+        .unwrap_err();
+
+    assert!(error.0.contains("{}"), "{error}");
+    assert_eq!(github.lock().unwrap().labels, ["bug", "ready"]);
+}
+
+#[tokio::test]
+async fn reads_actuals_from_labels() {
+    let (base_url, _) = github(&["bug", "actual: 6"]).await;
+
+    let actual = client(&base_url, "estimate: {}", "actual: {}")
+        .fetch_actual("acme/api#42")
+        .await
+        .unwrap();
+
+    assert_eq!(actual, Some(6.0));
+}
+
+#[tokio::test]
+async fn rejects_keys_that_arent_issues() {
+    let (base_url, _) = github(&[]).await;
+
+    let error = client(&base_url, "estimate: {}", "")
+        .write_estimate("PROJ-1", "8")
+        .await
+        .unwrap_err();
+
+    assert!(error.0.contains("isn't a GitHub issue"), "{error}");
+}
+
+#[tokio::test]
+async fn writes_estimates_to_a_projects_field() {
+    let (base_url, github) = github(&[]).await;
+
+    client(&base_url, "project:3:Estimate", "")
+        .write_estimate("acme/api#42", "5")
+        .await
+        .unwrap();
+
+    let github = github.lock().unwrap();
+    assert_eq!(
+        github.mutations,
+        [json!({
+            "project": "PROJECT_3",
+            "item": "ITEM_3",
+            "field": "FIELD_Estimate",
+            "value": 5.0,
+        })]
+    );
+}
+
+#[tokio::test]
+async fn project_writes_need_a_number_and_a_field() {
+    let (base_url, github) = github(&[]).await;
+
+    let not_a_number = client(&base_url, "project:3:Estimate", "")
+        .write_estimate("acme/api#42", "?")
+        .await
+        .unwrap_err();
+    let no_such_field = client(&base_url, "project:2:Estimate", "")
+        .write_estimate("acme/api#42", "5")
+        .await
+        .unwrap_err();
+    let not_in_project = client(&base_url, "project:9:Estimate", "")
+        .write_estimate("acme/api#42", "5")
+        .await
+        .unwrap_err();
+    let rejected = client(&base_url, "project:3:Points", "")
+        .write_estimate("acme/api#42", "5")
+        .await
+        .unwrap_err();
+
+    assert!(not_a_number.0.contains("number field"), "{not_a_number}");
+    assert!(
+        no_such_field.0.contains("no number field"),
+        "{no_such_field}"
+    );
+    assert!(not_in_project.0.contains("project 9"), "{not_in_project}");
+    assert!(rejected.0.contains("No field called Points"), "{rejected}");
+    assert!(github.lock().unwrap().mutations.is_empty());
+}
+
+#[tokio::test]
+async fn reads_actuals_from_a_projects_field() {
+    let (base_url, _) = github(&[]).await;
+
+    let actual = client(&base_url, "estimate: {}", "project:3:Actual")
+        .fetch_actual("acme/api#42")
+        .await
+        .unwrap();
+    let unset = client(&base_url, "estimate: {}", "project:3:Estimate")
+        .fetch_actual("acme/api#42")
+        .await
+        .unwrap();
+
+    assert_eq!(actual, Some(6.0));
+    assert_eq!(unset, None);
+}
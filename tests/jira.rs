@@ -0,0 +1,252 @@
+mod common;
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    routing::{get, put},
+};
+use base64::{Engine, prelude::BASE64_STANDARD};
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use guess_rs::{
+    domain::issue_tracker::IssueTracker,
+    infrastructure::trackers::jira::JiraClient,
+    jobs::tracker_sync::{ATTEMPTS, write_back},
+};
+
+/// What the stand-in Jira was asked to do.
+#[derive(Default)]
+struct Jira {
+    searches: Vec<HashMap<String, String>>,
+    updates: Vec<(String, Value)>,
+    /// How many updates fail with a 503 before they go through.
+    outages: usize,
+}
+
+type Shared = Arc<Mutex<Jira>>;
+
+async fn jira(outages: usize) -> (String, Shared) {
+    let shared = Arc::new(Mutex::new(Jira {
+        outages,
+        ..Default::default()
+    }));
+    let router = Router::new()
+        .route("/rest/api/3/search/jql", get(search))
+        .route("/rest/api/3/issue/{key}", put(update).get(issue))
+        .with_state(shared.clone());
+
+    (common::serve(router).await, shared)
+}
+
+fn client(base_url: &str) -> JiraClient {
+    JiraClient {
+        http: Client::new(),
+        base_url: base_url.to_string(),
+        email: "ann@example.com".to_string(),
+        api_token: "secret".to_string(),
+        jql: "project = PROJ".to_string(),
+        estimate_field: "customfield_10016".to_string(),
+        actual_field: "customfield_10020".to_string(),
+    }
+}
+
+fn is_signed_in(headers: &HeaderMap) -> bool {
+    let expected = format!("Basic {}", BASE64_STANDARD.encode("ann@example.com:secret"));
+    headers
+        .get("authorization")
+        .is_some_and(|h| h == expected.as_str())
+}
+
+/// Two pages of issues, the second one reached with a page token.
+async fn search(
+    State(jira): State<Shared>,
+    headers: HeaderMap,
+    Query(query): Query<HashMap<String, String>>,
+) -> Result<Json<Value>, StatusCode> {
+    if !is_signed_in(&headers) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    let page = match query.get("nextPageToken").map(String::as_str) {
+        None => json!({
+            "issues": [
+                { "key": "PROJ-1", "fields": { "summary": "Sign in" } },
+                { "key": "PROJ-2", "fields": { "summary": "Sign out" } },
+            ],
+            "nextPageToken": "page-2",
+        }),
+        Some("page-2") => json!({
+            "issues": [{ "key": "PROJ-3", "fields": { "summary": "Reset password" } }],
+        }),
+        Some(_) => return Err(StatusCode::BAD_REQUEST),
+    };
+    jira.lock().unwrap().searches.push(query);
+
+    Ok(Json(page))
+}
+
+async fn update(
+    State(jira): State<Shared>,
+    headers: HeaderMap,
+    Path(key): Path<String>,
+    Json(body): Json<Value>,
+) -> StatusCode {
+    if !is_signed_in(&headers) {
+        return StatusCode::UNAUTHORIZED;
+    }
+    if key == "PROJ-404" {
+        return StatusCode::NOT_FOUND;
+    }
+
+    let mut jira = jira.lock().unwrap();
+    jira.updates.push((key, body));
+    match jira.outages {
+        0 => StatusCode::NO_CONTENT,
+        _ => {
+            jira.outages -= 1;
+            StatusCode::SERVICE_UNAVAILABLE
+        }
+    }
+}
+
+async fn issue(Path(key): Path<String>) -> Result<Json<Value>, StatusCode> {
+    let actual = match key.as_str() {
+        "PROJ-1" => json!(5),
+        "PROJ-2" => json!(" 2.5 "),
+        "PROJ-3" => Value::Null,
+        "PROJ-4" => json!("a lot"),
+        _ => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+    };
+
+    Ok(Json(json!({
+        "key": key,
+        "fields": { "customfield_10020": actual, "timespent": 9000 },
+    })))
+}
+
+#[tokio::test]
+async fn imports_every_page_of_issues() {
+    let (base_url, jira) = jira(0).await;
+
+    let issues = client(&base_url).fetch_issues().await.unwrap();
+
+    let keys: Vec<&str> = issues.iter().map(|i| i.key.as_str()).collect();
+    assert_eq!(keys, ["PROJ-1", "PROJ-2", "PROJ-3"]);
+    assert_eq!(issues[2].title, "Reset password");
+    assert_eq!(issues[2].url, format!("{base_url}/browse/PROJ-3"));
+
+    let jira = jira.lock().unwrap();
+    assert_eq!(jira.searches.len(), 2);
+    assert_eq!(jira.searches[0]["jql"], "project = PROJ");
+    assert_eq!(jira.searches[1]["nextPageToken"], "page-2");
+}
+
+#[tokio::test]
+async fn import_fails_when_the_token_is_rejected() {
+    let (base_url, _) = jira(0).await;
+    let mut client = client(&base_url);
+    client.api_token = "wrong".to_string();
+
+    let error = client.fetch_issues().await.unwrap_err();
+
+    assert!(error.0.contains("401"), "{error}");
+}
+
+#[tokio::test]
+async fn writes_numeric_estimates_as_numbers() {
+    let (base_url, jira) = jira(0).await;
+    let client = client(&base_url);
+
+    client.write_estimate("PROJ-1", "5").await.unwrap();
+    client.write_estimate("PROJ-2", "?").await.unwrap();
+
+    let jira = jira.lock().unwrap();
+    assert_eq!(
+        jira.updates,
+        [
+            (
+                "PROJ-1".to_string(),
+                json!({ "fields": { "customfield_10016": 5.0 } })
+            ),
+            (
+                "PROJ-2".to_string(),
+                json!({ "fields": { "customfield_10016": "?" } })
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn write_back_retries_until_jira_recovers() {
+    let (base_url, jira) = jira(2).await;
+
+    write_back(&client(&base_url), "PROJ-1", "3", Duration::from_millis(1))
+        .await
+        .unwrap();
+
+    assert_eq!(jira.lock().unwrap().updates.len(), 3);
+}
+
+#[tokio::test]
+async fn write_back_gives_up_on_server_errors() {
+    let (base_url, jira) = jira(usize::MAX).await;
+
+    let error = write_back(&client(&base_url), "PROJ-1", "3", Duration::from_millis(1))
+        .await
+        .unwrap_err();
+
+    assert!(error.0.contains("503"), "{error}");
+    assert_eq!(jira.lock().unwrap().updates.len(), ATTEMPTS as usize);
+}
+
+#[tokio::test]
+async fn write_back_reports_client_errors() {
+    let (base_url, _) = jira(0).await;
+
+    let error = write_back(
+        &client(&base_url),
+        "PROJ-404",
+        "3",
+        Duration::from_millis(1),
+    )
+    .await
+    .unwrap_err();
+
+    assert!(error.0.contains("PROJ-404"), "{error}");
+    assert!(error.0.contains("404"), "{error}");
+}
+
+#[tokio::test]
+async fn reads_actuals_from_number_and_text_fields() {
+    let (base_url, _) = jira(0).await;
+    let client = client(&base_url);
+
+    assert_eq!(client.fetch_actual("PROJ-1").await.unwrap(), Some(5.0));
+    assert_eq!(client.fetch_actual("PROJ-2").await.unwrap(), Some(2.5));
+    assert_eq!(client.fetch_actual("PROJ-3").await.unwrap(), None);
+    assert!(client.fetch_actual("PROJ-4").await.is_err());
+    assert!(
+        client
+            .fetch_actual("PROJ-5")
+            .await
+            .unwrap_err()
+            .0
+            .contains("500")
+    );
+}
+
+#[tokio::test]
+async fn reads_logged_work_in_hours() {
+    let (base_url, _) = jira(0).await;
+    let mut client = client(&base_url);
+    client.actual_field = "timespent".to_string();
+
+    assert_eq!(client.fetch_actual("PROJ-1").await.unwrap(), Some(2.5));
+}
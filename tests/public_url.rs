@@ -0,0 +1,75 @@
+mod common;
+
+use axum::{Router, routing::get};
+use reqwest::Client;
+use std::time::Duration;
+
+use guess_rs::util::url::{parse_public_url, public_client};
+
+#[test]
+fn accepts_public_https_urls() {
+    for url in [
+        "https://acme.atlassian.net",
+        "https://api.github.com/",
+        "https://hooks.slack.com/services/T0/B0/x",
+        "https://8.8.8.8/hook",
+        "https://[2606:4700::1111]/hook",
+    ] {
+        assert!(parse_public_url(url).is_ok(), "{url}");
+    }
+}
+
+#[test]
+fn rejects_cleartext_and_other_schemes() {
+    for url in [
+        "http://acme.atlassian.net",
+        "ftp://acme.atlassian.net",
+        "acme.atlassian.net",
+        "https://",
+    ] {
+        assert!(parse_public_url(url).is_err(), "{url}");
+    }
+}
+
+#[test]
+fn rejects_local_and_private_hosts() {
+    for url in [
+        "https://localhost:8080",
+        "https://LOCALHOST",
+        "https://admin.localhost",
+        "https://localhost.",
+        "https://127.0.0.1",
+        "https://127.1",
+        "https://2130706433",
+        "https://0.0.0.0",
+        "https://10.0.0.5",
+        "https://172.16.3.4",
+        "https://192.168.1.1",
+        "https://169.254.169.254/latest/meta-data",
+        "https://100.64.0.1",
+        "https://[::1]",
+        "https://[::]",
+        "https://[fd00::1]",
+        "https://[fe80::1]",
+        "https://[::ffff:127.0.0.1]",
+        "https://[::ffff:169.254.169.254]",
+    ] {
+        assert!(parse_public_url(url).is_err(), "{url}");
+    }
+}
+
+#[tokio::test]
+async fn public_client_wont_connect_to_local_hosts() {
+    let router = Router::new().route("/", get(|| async { "hello" }));
+    let url = common::serve(router)
+        .await
+        .replace("127.0.0.1", "localhost");
+
+    assert!(Client::new().get(&url).send().await.is_ok());
+    let error = public_client(Duration::from_secs(5))
+        .get(&url)
+        .send()
+        .await
+        .unwrap_err();
+    assert!(error.is_connect(), "{error}");
+}
@@ -0,0 +1,225 @@
+mod common;
+
+use axum::{
+    Json, Router,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::post,
+};
+use chrono::{DateTime, Duration, Utc};
+use reqwest::Client;
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+
+use guess_rs::{
+    application::SlackService,
+    domain::{
+        breakout::Breakout,
+        notification::Notifier,
+        room_event::{EstimateAccepted, SessionEnded, StorySummary},
+    },
+    infrastructure::chat::slack::SlackNotifier,
+};
+
+/// A slash command as Slack signs and sends it, taken from Slack's
+/// "Verifying requests from Slack" guide along with its secret.
+const SIGNING_SECRET: &str = "8f742231b10e8888abcd99yyyzzz85a5";
+const TIMESTAMP: &str = "1531420618";
+const SIGNATURE: &str = "v0=a2114d57b48eac39b9ad189dd8316235a7b4a8d21a10bd27519666489c69b503";
+const BODY: &str = "token=xyzz0WbapA4vBCDEFasx0q6G&team_id=T1DC2JH3J&team_domain=testteamnow&channel_id=G8PSS9T3V&channel_name=foobar&user_id=U2CERLKJA&user_name=roadrunner&command=%2Fwebhook-collect&text=&response_url=https%3A%2F%2Fhooks.slack.com%2Fcommands%2FT1DC2JH3J%2F397700885554%2F96rGlfmibIGlgcZRskXaIFfN&trigger_id=398738663015.47445629121.803a0bc887a14d10d2c447fce8b6703c";
+const SENT_AT: i64 = 1531420618;
+
+fn slack() -> SlackService {
+    SlackService::with_signing_secret(SIGNING_SECRET)
+}
+
+#[test]
+fn accepts_a_signed_slash_command() {
+    let slack = slack();
+
+    assert!(slack.verify_at(SENT_AT, TIMESTAMP, SIGNATURE, BODY).is_ok());
+    assert!(
+        slack
+            .verify_at(SENT_AT + 300, TIMESTAMP, SIGNATURE, BODY)
+            .is_ok()
+    );
+}
+
+#[test]
+fn rejects_stale_slash_commands() {
+    let slack = slack();
+
+    for now in [SENT_AT + 301, SENT_AT - 301, i64::MAX, i64::MIN] {
+        assert!(
+            slack.verify_at(now, TIMESTAMP, SIGNATURE, BODY).is_err(),
+            "{now}"
+        );
+    }
+    for timestamp in ["-9223372036854775808", "9223372036854775807", "soon", ""] {
+        assert!(
+            slack
+                .verify_at(SENT_AT, timestamp, SIGNATURE, BODY)
+                .is_err(),
+            "{timestamp}"
+        );
+    }
+}
+
+#[test]
+fn rejects_tampered_slash_commands() {
+    let slack = slack();
+    let body = BODY.replace("text=&", "text=Sprint+42&");
+    let signature = SIGNATURE.replace("a2114d", "a2114e");
+
+    assert!(
+        slack
+            .verify_at(SENT_AT, TIMESTAMP, SIGNATURE, &body)
+            .is_err()
+    );
+    assert!(
+        slack
+            .verify_at(SENT_AT, TIMESTAMP, &signature, BODY)
+            .is_err()
+    );
+    assert!(
+        slack
+            .verify_at(SENT_AT, TIMESTAMP, &SIGNATURE[3..], BODY)
+            .is_err()
+    );
+    assert!(
+        SlackService::with_signing_secret("another secret")
+            .verify_at(SENT_AT, TIMESTAMP, SIGNATURE, BODY)
+            .is_err()
+    );
+}
+
+/// Messages the stand-in incoming webhook was sent.
+type Received = Arc<Mutex<Vec<Value>>>;
+
+async fn receiver(status: StatusCode) -> (String, Received) {
+    let received = Received::default();
+    let router = Router::new()
+        .route(
+            "/services/T0/B0/x",
+            post(
+                move |State(received): State<Received>,
+                      headers: HeaderMap,
+                      Json(message): Json<Value>| async move {
+                    assert_eq!(headers["content-type"], "application/json");
+                    received.lock().unwrap().push(message);
+                    status
+                },
+            ),
+        )
+        .with_state(received.clone());
+
+    (common::serve(router).await, received)
+}
+
+fn breakout() -> Breakout {
+    let now = Utc::now().naive_utc();
+    Breakout {
+        id: 1,
+        lookup_id: "lookup".to_string(),
+        created_at: now,
+        updated_at: now,
+        title: "Sprint <42>".to_string(),
+        description: String::new(),
+        facilitator_id: None,
+        facilitator_only_controls: false,
+        code: "brave-otter-482193".to_string(),
+        display_token: String::new(),
+        auto_reveal: false,
+        auto_reveal_countdown: 0,
+        timer_expiry: Default::default(),
+        intake_token: String::new(),
+        slack_webhook_url: None,
+        teams_webhook_url: None,
+        discord_webhook_url: None,
+        team_id: None,
+        estimation_mode: Default::default(),
+        started_in_slack: true,
+        facilitator_token: None,
+    }
+}
+
+fn accepted(title: &str, key: Option<&str>, estimate: &str, at: DateTime<Utc>) -> EstimateAccepted {
+    EstimateAccepted {
+        story: StorySummary {
+            id: 1,
+            title: title.to_string(),
+            external_key: key.map(str::to_string),
+            external_url: key.map(|key| format!("https://acme.atlassian.net/browse/{key}")),
+        },
+        estimate: estimate.to_string(),
+        reason: None,
+        accepted_at: at,
+    }
+}
+
+fn session() -> SessionEnded {
+    let started_at = Utc::now() - Duration::minutes(12);
+    SessionEnded {
+        started_at,
+        ended_at: started_at + Duration::minutes(12),
+        rounds: 3,
+        estimates: vec![
+            accepted("Sign in", Some("PROJ-1"), "5", started_at),
+            accepted("Fix <script>", None, "?", started_at),
+        ],
+    }
+}
+
+#[tokio::test]
+async fn posts_the_session_summary() {
+    let (base_url, received) = receiver(StatusCode::OK).await;
+    let notifier = SlackNotifier {
+        http: Client::new(),
+        webhook_url: format!("{base_url}/services/T0/B0/x"),
+    };
+
+    notifier
+        .session_summary(
+            &breakout(),
+            "https://guess.example/breakout/lookup",
+            &session(),
+        )
+        .await
+        .unwrap();
+
+    let received = received.lock().unwrap();
+    assert_eq!(received.len(), 1);
+    let blocks = received[0]["blocks"].as_array().unwrap();
+    assert_eq!(
+        blocks[0]["text"]["text"],
+        "*<https://guess.example/breakout/lookup|Sprint &lt;42&gt;>*\n\
+         Wrapped up after 12 minutes: 3 rounds, 2 stories estimated."
+    );
+    assert_eq!(
+        blocks[1]["text"]["text"],
+        "• <https://acme.atlassian.net/browse/PROJ-1|PROJ-1> Sign in: *5*\n\
+         • Fix &lt;script&gt;: *?*"
+    );
+    assert_eq!(received[0]["text"], blocks[0]["text"]["text"]);
+}
+
+#[tokio::test]
+async fn reports_a_webhook_that_fails() {
+    let (base_url, received) = receiver(StatusCode::NOT_FOUND).await;
+    let notifier = SlackNotifier {
+        http: Client::new(),
+        webhook_url: format!("{base_url}/services/T0/B0/x"),
+    };
+
+    let error = notifier
+        .session_summary(
+            &breakout(),
+            "https://guess.example/breakout/lookup",
+            &session(),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(error.0.contains("404"), "{error}");
+    assert_eq!(received.lock().unwrap().len(), 1);
+}
@@ -0,0 +1,49 @@
+use guess_rs::domain::issue_tracker::{SaveTracker, TrackerKind};
+
+fn github(estimate_field: &str) -> SaveTracker {
+    SaveTracker {
+        breakout_id: 1,
+        kind: TrackerKind::GitHub,
+        base_url: "https://api.github.com/".to_string(),
+        secret: "ghp_secret".to_string(),
+        query: "repo:acme/api".to_string(),
+        estimate_field: estimate_field.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn accepts_labels_and_projects_fields() {
+    for field in ["estimate: {}", "{} points", "sp-{}", "project:3:Estimate"] {
+        let saved = github(field).validated();
+        assert!(saved.is_ok(), "{field}");
+        assert_eq!(saved.unwrap().base_url, "https://api.github.com");
+    }
+}
+
+#[test]
+fn rejects_labels_that_would_match_every_label() {
+    for field in ["{}", " {} ", "{}{}"] {
+        assert!(github(field).validated().is_err(), "{field}");
+    }
+}
+
+#[test]
+fn rejects_control_characters_in_the_estimate_field() {
+    assert!(github("estimate:\u{7}{}").validated().is_err());
+}
+
+#[test]
+fn rejects_cleartext_and_private_tracker_urls() {
+    for base_url in [
+        "http://api.github.com",
+        "https://10.0.0.8",
+        "https://localhost",
+    ] {
+        let tracker = SaveTracker {
+            base_url: base_url.to_string(),
+            ..github("estimate: {}")
+        };
+        assert!(tracker.validated().is_err(), "{base_url}");
+    }
+}